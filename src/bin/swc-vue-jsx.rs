@@ -0,0 +1,239 @@
+use std::{
+    env, fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process,
+};
+
+use serde_json::{Map, Value};
+use swc_plugin_vue_jsx::{transform, Diagnostic, PluginOptions};
+
+const USAGE: &str = "\
+Compile Vue JSX files to JavaScript
+
+Usage: swc-vue-jsx [OPTIONS] <INPUT>...
+
+Arguments:
+  <INPUT>...  `.jsx` / `.tsx` files or directories
+
+Options:
+  -c, --config <FILE>      PluginOptions JSON file
+      --options <JSON>     PluginOptions JSON, overrides the config file
+      --target <TARGET>    vue3 | vue2.7 | vapor
+      --vue-version <VER>  targeted Vue runtime, e.g. 3.4
+      --jsx-runtime <RT>   optimized | automatic
+      --ssr                compile setup render functions for ssr
+      --no-hoist           don't hoist static vnodes to module scope
+  -o, --out-dir <DIR>      write `.js` files to directory instead of stdout
+  -s, --source-maps        write `.js.map` files next to outputs, requires --out-dir
+  -h, --help               print help";
+
+#[derive(Default)]
+struct Args {
+    inputs: Vec<PathBuf>,
+    config: Option<PathBuf>,
+    options: Map<String, Value>,
+    out_dir: Option<PathBuf>,
+    source_maps: bool,
+}
+
+impl Args {
+    fn parse() -> Result<Self, String> {
+        let mut args = Self::default();
+        let mut iter = env::args().skip(1);
+
+        while let Some(arg) = iter.next() {
+            let mut value = |name: &str| {
+                iter.next()
+                    .ok_or_else(|| format!("missing value for `{name}`"))
+            };
+
+            match arg.as_str() {
+                "-h" | "--help" => {
+                    println!("{USAGE}");
+                    process::exit(0)
+                },
+                "-c" | "--config" => args.config = Some(value(&arg)?.into()),
+                "--options" => {
+                    let json = value(&arg)?;
+                    let options: Map<String, Value> = serde_json::from_str(&json)
+                        .map_err(|err| format!("invalid `--options`: {err}"))?;
+                    args.options.extend(options)
+                },
+                "--target" => args.set("target", value(&arg)?),
+                "--vue-version" => args.set("vueVersion", value(&arg)?),
+                "--jsx-runtime" => args.set("jsxRuntime", value(&arg)?),
+                "--ssr" => args.set("ssr", true),
+                "--no-hoist" => args.set("hoist", false),
+                "-o" | "--out-dir" => args.out_dir = Some(value(&arg)?.into()),
+                "-s" | "--source-maps" => args.source_maps = true,
+                flag if flag.starts_with('-') => return Err(format!("unknown option `{flag}`")),
+                input => args.inputs.push(input.into()),
+            }
+        }
+
+        if args.inputs.is_empty() {
+            return Err("no input".into());
+        }
+
+        if args.source_maps && args.out_dir.is_none() {
+            return Err("`--source-maps` requires `--out-dir`".into());
+        }
+
+        Ok(args)
+    }
+
+    fn set(&mut self, key: &str, value: impl Into<Value>) {
+        self.options.insert(key.into(), value.into());
+    }
+
+    /// config file, then `--options`, then flags
+    fn plugin_options(&self) -> Result<PluginOptions, String> {
+        let mut options = match &self.config {
+            Some(path) => {
+                let json =
+                    fs::read_to_string(path).map_err(|err| format!("{}: {err}", path.display()))?;
+
+                serde_json::from_str(&json).map_err(|err| format!("{}: {err}", path.display()))?
+            },
+            None => Map::new(),
+        };
+
+        options.extend(self.options.clone());
+
+        PluginOptions::parse(&Value::Object(options).to_string())
+            .map_err(|err| format!("invalid options, {err}"))
+    }
+}
+
+fn is_jsx_file(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|ext| ext.to_str()),
+        Some("jsx" | "tsx")
+    )
+}
+
+/// `(file, path relative to the input)` of JSX files, sorted for stable output
+fn collect_files(input: &Path) -> io::Result<Vec<(PathBuf, PathBuf)>> {
+    if !input.is_dir() {
+        let name = input.file_name().map(PathBuf::from).unwrap_or_default();
+
+        return Ok(vec![(input.to_path_buf(), name)]);
+    }
+
+    let mut files = vec![];
+    let mut dirs = vec![input.to_path_buf()];
+
+    while let Some(dir) = dirs.pop() {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+
+            let is_hidden = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .map_or(false, |name| {
+                    name.starts_with('.') || name == "node_modules"
+                });
+
+            if is_hidden {
+                continue;
+            }
+
+            if path.is_dir() {
+                dirs.push(path)
+            } else if is_jsx_file(&path) {
+                let relative = path.strip_prefix(input).unwrap_or(&path).to_path_buf();
+
+                files.push((path, relative))
+            }
+        }
+    }
+
+    files.sort();
+
+    Ok(files)
+}
+
+fn compile(file: &Path, relative: &Path, opts: PluginOptions, args: &Args) -> Result<(), String> {
+    let source = fs::read_to_string(file).map_err(|err| format!("{}: {err}", file.display()))?;
+
+    let filename = file.to_string_lossy();
+
+    let output = transform(&source, &filename, opts).map_err(|err| format!("{filename}: {err}"))?;
+
+    for diagnostic in &output.diagnostics {
+        match diagnostic.location {
+            Some(_) => eprintln!("{filename}:{diagnostic}"),
+            None => eprintln!("{filename}: {diagnostic}"),
+        }
+    }
+
+    if output.diagnostics.iter().any(Diagnostic::is_error) {
+        return Err(format!("{filename}: failed to compile"));
+    }
+
+    match &args.out_dir {
+        Some(out_dir) => {
+            let out = out_dir.join(relative).with_extension("js");
+
+            if let Some(parent) = out.parent() {
+                fs::create_dir_all(parent).map_err(|err| format!("{}: {err}", parent.display()))?;
+            }
+
+            let mut code = output.code;
+
+            if args.source_maps {
+                let map = out.with_extension("js.map");
+
+                fs::write(&map, output.map).map_err(|err| format!("{}: {err}", map.display()))?;
+
+                let name = map.file_name().unwrap_or_default().to_string_lossy();
+
+                code.push_str(&format!("//# sourceMappingURL={name}\n"));
+            }
+
+            fs::write(&out, code).map_err(|err| format!("{}: {err}", out.display()))
+        },
+        None => {
+            let mut stdout = io::stdout().lock();
+
+            if args.inputs.len() > 1 || args.inputs[0].is_dir() {
+                writeln!(stdout, "// {filename}").map_err(|err| err.to_string())?;
+            }
+
+            write!(stdout, "{}", output.code).map_err(|err| err.to_string())
+        },
+    }
+}
+
+fn run() -> Result<bool, String> {
+    let args = Args::parse().map_err(|err| format!("{err}\n\n{USAGE}"))?;
+
+    let opts = args.plugin_options()?;
+
+    let mut ok = true;
+
+    for input in &args.inputs {
+        let files = collect_files(input).map_err(|err| format!("{}: {err}", input.display()))?;
+
+        for (file, relative) in files {
+            if let Err(err) = compile(&file, &relative, opts.clone(), &args) {
+                eprintln!("{err}");
+                ok = false
+            }
+        }
+    }
+
+    Ok(ok)
+}
+
+fn main() {
+    match run() {
+        Ok(true) => {},
+        Ok(false) => process::exit(1),
+        Err(err) => {
+            eprintln!("error: {err}");
+            process::exit(2)
+        },
+    }
+}
@@ -1,3 +1,4 @@
+use phf::{phf_set, Set};
 use swc_core::{
     common::DUMMY_SP,
     ecma::ast::{Expr, Lit, Null},
@@ -6,6 +7,8 @@ use swc_core::{
 pub const NULL_EXPR: Expr = Expr::Lit(Lit::Null(Null { span: DUMMY_SP }));
 
 pub const REF: &str = "ref";
+pub const REF_FOR: &str = "ref_for";
+pub const REF_KEY: &str = "ref_key";
 pub const KEY: &str = "key";
 pub const CLASS: &str = "class";
 pub const STYLE: &str = "style";
@@ -24,8 +27,43 @@ pub const V_SLOTS: &str = "v-slots";
 
 pub const V_SHOW: &str = "v-show";
 
+/// compiler hints, not rendered
+pub const V_STATIC: &str = "v-static";
+pub const V_DYNAMIC: &str = "v-dynamic";
+
 pub const FRAGMENT: &str = "Fragment";
 
+pub const SERVER_RENDERER: &str = "vue/server-renderer";
+
+pub const SETUP: &str = "setup";
+pub const RENDER: &str = "render";
+pub const DEFINE_COMPONENT: &str = "defineComponent";
+
 // pub const KEEP_ALIVE: &str = "KeepAlive";
 
 pub const V_MODEL_NATIVE_ELEMENT: &[&str; 3] = &["input", "textarea", "select"];
+
+/// ## [Void elements](https://html.spec.whatwg.org/multipage/syntax.html#void-elements)
+pub const VOID_ELEMENT: &Set<&str> = &phf_set! {
+    "area","base","br","col","embed","hr","img","input","link","meta","source","track","wbr",
+};
+
+/// elements dropped by html parser outside of table context
+pub const NON_STRINGIFIABLE_ELEMENT: &Set<&str> = &phf_set! {
+    "caption","col","colgroup","tbody","td","tfoot","th","thead","tr",
+};
+
+/// elements whose text content must not be escaped
+pub const RAW_TEXT_ELEMENT: &Set<&str> = &phf_set! {
+    "script","style","noscript","iframe","xmp","noembed","noframes",
+};
+
+/// ## [Elements close p](https://html.spec.whatwg.org/multipage/grouping-content.html#the-p-element)
+pub const CLOSE_P_ELEMENT: &Set<&str> = &phf_set! {
+    "address","article","aside","blockquote","details","dialog","div","dl","fieldset",
+    "figcaption","figure","footer","form","h1","h2","h3","h4","h5","h6",
+    "header","hgroup","hr","main","menu","nav","ol","p","pre","section","table","ul",
+};
+
+/// DOM properties which are not reflected by html attributes
+pub const DOM_PROP: &[&str; 3] = &["innerHTML", "textContent", "innerText"];
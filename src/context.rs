@@ -11,6 +11,8 @@ use swc_core::{
 
 use crate::{
     args,
+    constant::SERVER_RENDERER,
+    hoist::scope::{FnKind, FnScope},
     shared::{expr::ExprExtend, ident::IdentExtend},
     VueJSX,
 };
@@ -20,8 +22,34 @@ pub trait Context {
 
     fn is_custom_element(&self, text: &str) -> bool;
 
+    fn is_custom_renderer(&self) -> bool;
+
+    fn is_vue_fragment(&self, ident: &Ident) -> bool;
+
+    /// element is rendered once per item of a loop
+    fn is_in_loop(&self) -> bool;
+
+    /// children of a native `<p>`, html parser auto closes it before block elements
+    fn is_in_paragraph(&self) -> bool;
+
+    fn set_in_paragraph(&mut self, in_paragraph: bool);
+
+    /// descendants of a `v-dynamic` element, nothing is hoisted
+    fn is_in_dynamic(&self) -> bool;
+
+    fn set_in_dynamic(&mut self, in_dynamic: bool);
+
+    /// top-level ref binding of the enclosing `setup`
+    fn setup_binding(&self, name: &str) -> Option<Ident>;
+
+    fn is_vue_at_least(&self, major: u32, minor: u32) -> bool;
+
     fn import_from_vue(&mut self, name: &'static str) -> Ident;
 
+    fn import_ssr_helper(&mut self, name: &'static str) -> Ident;
+
+    fn import_jsx_runtime(&mut self, name: &'static str) -> Ident;
+
     fn get_ident(&mut self, name: &'static str) -> Ident;
 
     fn add_pure_comment(&self, pos: BytePos);
@@ -30,7 +58,15 @@ pub trait Context {
 
     fn static_threshold(&self) -> usize;
 
-    fn hoist_to_module(&mut self, expr: Expr) -> Ident;
+    fn static_size_limit(&self) -> usize;
+
+    fn is_merge_props(&self) -> bool;
+
+    fn is_hoist(&self) -> bool;
+
+    fn has_helper(&self, name: &str) -> bool;
+
+    fn hoist_to_module(&mut self, expr: Expr) -> Expr;
 
     fn hoist_to_scope(&mut self, expr: Expr) -> Ident;
 
@@ -46,7 +82,11 @@ pub trait Context {
     }
 
     fn create_element_vnode(&mut self, args: Vec<ExprOrSpread>) -> Expr {
-        self.invoke("createElementVNode", args)
+        if self.has_helper("createElementVNode") {
+            self.invoke("createElementVNode", args)
+        } else {
+            self.create_vnode(args)
+        }
     }
 
     fn create_vnode(&mut self, args: Vec<ExprOrSpread>) -> Expr {
@@ -64,19 +104,33 @@ pub trait Context {
     where
         Self: Sized,
     {
-        self.invoke("createTextVNode", args).hoist_to_module(self)
+        let text_vnode = self.invoke("createTextVNode", args);
+
+        if self.is_in_dynamic() {
+            return text_vnode;
+        }
+
+        text_vnode.hoist_to_module(self)
     }
 
     fn merge_props(&mut self, args: Vec<ExprOrSpread>) -> Expr {
         self.invoke("mergeProps", args)
     }
 
+    fn normalize_class(&mut self, args: Vec<ExprOrSpread>) -> Expr {
+        self.invoke("normalizeClass", args)
+    }
+
+    fn normalize_style(&mut self, args: Vec<ExprOrSpread>) -> Expr {
+        self.invoke("normalizeStyle", args)
+    }
+
     fn with_directive(&mut self, args: Vec<ExprOrSpread>) -> Expr {
         self.invoke("withDirectives", args)
     }
 }
 
-impl<'a> Context for VueJSX<'a> {
+impl<'a, C: Comments> Context for VueJSX<'a, C> {
     fn is_unresolved(&self, ident: &Ident) -> bool {
         ident.span.has_mark(self.unresolved_mark)
     }
@@ -85,8 +139,65 @@ impl<'a> Context for VueJSX<'a> {
         self.custom_tag_regex.is_match(text)
     }
 
+    fn is_custom_renderer(&self) -> bool {
+        self.custom_renderer
+    }
+
+    fn is_vue_fragment(&self, ident: &Ident) -> bool {
+        self.vue_fragments.contains(&ident.to_id())
+    }
+
+    fn is_in_loop(&self) -> bool {
+        self.loop_depth > 0 || self.fn_scopes.last().map_or(false, |scope| scope.in_loop)
+    }
+
+    fn is_in_paragraph(&self) -> bool {
+        self.in_paragraph
+    }
+
+    fn set_in_paragraph(&mut self, in_paragraph: bool) {
+        self.in_paragraph = in_paragraph
+    }
+
+    fn is_in_dynamic(&self) -> bool {
+        self.in_dynamic
+    }
+
+    fn set_in_dynamic(&mut self, in_dynamic: bool) {
+        self.in_dynamic = in_dynamic
+    }
+
+    fn setup_binding(&self, name: &str) -> Option<Ident> {
+        self.fn_scopes
+            .iter()
+            .rev()
+            .find(|scope| scope.kind == FnKind::Setup)?
+            .bindings
+            .iter()
+            .find(|ident| &*ident.sym == name)
+            .cloned()
+    }
+
+    fn is_vue_at_least(&self, major: u32, minor: u32) -> bool {
+        self.vue_version.is_at_least(major, minor)
+    }
+
     fn import_from_vue(&mut self, name: &'static str) -> Ident {
-        self.import_helper.get_or_import(name, "vue").clone()
+        let path = self.helper_module(name, &self.runtime_module);
+
+        self.import_from(name, &path)
+    }
+
+    fn import_ssr_helper(&mut self, name: &'static str) -> Ident {
+        let path = self.helper_module(name, SERVER_RENDERER);
+
+        self.import_from(name, &path)
+    }
+
+    fn import_jsx_runtime(&mut self, name: &'static str) -> Ident {
+        let path = self.helper_module(name, &self.jsx_runtime_module);
+
+        self.import_from(name, &path)
     }
 
     fn get_ident(&mut self, name: &'static str) -> Ident {
@@ -108,11 +219,53 @@ impl<'a> Context for VueJSX<'a> {
         self.static_threshold
     }
 
-    fn hoist_to_module(&mut self, expr: Expr) -> Ident {
-        self.module_hoist.get_or_decl(expr).clone()
+    fn static_size_limit(&self) -> usize {
+        self.static_size_limit
+    }
+
+    fn is_merge_props(&self) -> bool {
+        self.merge_props
+    }
+
+    fn is_hoist(&self) -> bool {
+        self.hoist
+    }
+
+    fn has_helper(&self, name: &str) -> bool {
+        match name {
+            "createElementVNode" | "createElementBlock" => self.vue_version.is_at_least(3, 2),
+            _ => true,
+        }
+    }
+
+    fn hoist_to_module(&mut self, expr: Expr) -> Expr {
+        if !self.hoist {
+            return expr;
+        }
+
+        // usage inside function is deferred until the module is evaluated, unless it's an IIFE
+        let usage = self
+            .fn_scopes
+            .iter()
+            .all(|scope| scope.immediate)
+            .then_some(self.module_item_idx);
+
+        match self.module_hoist.get_or_decl(expr, usage) {
+            Ok(ident) => ident.into(),
+            Err(expr) => expr,
+        }
     }
 
     fn hoist_to_scope(&mut self, expr: Expr) -> Ident {
-        self.scope_hoist.get_or_decl(expr).clone()
+        let scope = self
+            .fn_scopes
+            .iter_mut()
+            .rev()
+            .find(|scope| scope.is_resolvable());
+
+        match scope {
+            Some(FnScope { hoist, .. }) => hoist.get_or_decl(expr).clone(),
+            None => self.scope_hoist.get_or_decl(expr).clone(),
+        }
     }
 }
@@ -0,0 +1,202 @@
+use std::mem;
+
+use indexmap::IndexMap;
+use swc_core::{
+    common::{util::take::Take, DUMMY_SP},
+    ecma::{
+        ast::{
+            ArrayLit, Expr, ExprOrSpread, Ident, JSXAttr, JSXAttrOrSpread, JSXMemberExpr, Lit,
+            ObjectLit, PropOrSpread, SpreadElement,
+        },
+        utils::ExprFactory,
+    },
+};
+use swc_helper_jsx_transform::{
+    attr::{key::Key, value::Value, Attr},
+    element::{tag::Tag, Element},
+    fragment::Fragment,
+    utils::is::is_event,
+    vnode::VNode,
+};
+
+use crate::{
+    args,
+    constant::{KEY, V_DYNAMIC, V_STATIC},
+    context::Context,
+    convert::element::{attr_value::jsx_str_value, merge_props::merge_listener},
+    shared::expr::ExprExtend,
+    utils::normalize::to_handler_key,
+};
+
+/// ## Automatic runtime
+/// runtime-agnostic `jsx(type, props, key)` calls, children are passed by `props.children`
+pub trait Automatic {
+    fn automatic<C: Context>(&self, ctx: &mut C) -> Expr;
+}
+
+impl<'a> Automatic for Element<'a> {
+    fn automatic<C: Context>(&self, ctx: &mut C) -> Expr {
+        let Element {
+            tag,
+            attrs,
+            children,
+            raw,
+            ..
+        } = self;
+
+        let type_expr = match tag {
+            Tag::Native(name) => Expr::from(*name),
+            // lowercase tags are intrinsic elements, e.g. custom elements
+            Tag::Extra(ident) if ident.sym.starts_with(|c: char| c.is_ascii_lowercase()) => {
+                Expr::from(&*ident.sym)
+            },
+            Tag::Extra(ident) => Ident::clone(ident).into(),
+            Tag::Member(member) => JSXMemberExpr::clone(member).into(),
+        };
+
+        let mut props = Vec::with_capacity(attrs.len() + 1);
+        // props since the last spread
+        let mut segment = Vec::new();
+        let mut key = None;
+
+        attrs.iter().zip(raw.opening.attrs.iter()).for_each(
+            |(
+                Attr {
+                    key: attr_key,
+                    value,
+                },
+                raw_attr,
+            )| {
+                let value = match raw_attr {
+                    // boolean shorthand, e.g. `<input disabled />`
+                    JSXAttrOrSpread::JSXAttr(JSXAttr { value: None, .. }) => true.into(),
+                    raw_attr => {
+                        match jsx_str_value(raw_attr) {
+                            Some(text) => Expr::from(text),
+                            None => attr_value(value, ctx),
+                        }
+                    },
+                };
+
+                match attr_key {
+                    Key::Attr(KEY) => key = Some(value),
+                    // compiler hints
+                    Key::Attr(V_STATIC | V_DYNAMIC) => {},
+                    Key::Attr(name) => segment.push((name.to_string(), value)),
+                    Key::NSAttr { ns, name } => segment.push((format!("{ns}:{name}"), value)),
+                    Key::Event(name) => segment.push((to_handler_key(name), value)),
+                    Key::Spread => {
+                        props.extend(dedupe_listeners(mem::take(&mut segment)));
+                        props.push(PropOrSpread::Spread(SpreadElement {
+                            dot3_token: DUMMY_SP,
+                            expr: Box::new(value),
+                        }))
+                    },
+                }
+            },
+        );
+
+        props.extend(dedupe_listeners(segment));
+
+        jsx_call(type_expr, props, children, key, ctx)
+    }
+}
+
+/// duplicate `on*` listeners are chained like the optimized runtime,
+/// the later one wins for other props, no runtime helpers are needed
+fn dedupe_listeners(props: Vec<(String, Expr)>) -> Vec<PropOrSpread> {
+    let mut prop_map: IndexMap<String, Expr> = IndexMap::with_capacity(props.len());
+
+    props.into_iter().for_each(|(name, next)| {
+        match prop_map.get_mut(&name) {
+            Some(prev) if is_event(&name) => *prev = merge_listener(prev.take(), next),
+            Some(prev) => *prev = next,
+            None => {
+                prop_map.insert(name, next);
+            },
+        }
+    });
+
+    prop_map
+        .into_iter()
+        .map(|(name, expr)| expr.into_prop(&name))
+        .collect()
+}
+
+impl<'a> Automatic for Fragment<'a> {
+    fn automatic<C: Context>(&self, ctx: &mut C) -> Expr {
+        let fragment = ctx.import_jsx_runtime("Fragment");
+
+        jsx_call(fragment.into(), Vec::new(), &self.children, None, ctx)
+    }
+}
+
+/// `jsx()` for single child, `jsxs()` for static children array
+fn jsx_call<C: Context>(
+    type_expr: Expr,
+    mut props: Vec<PropOrSpread>,
+    children: &[VNode],
+    key: Option<Expr>,
+    ctx: &mut C,
+) -> Expr {
+    let mut elems: Vec<Option<ExprOrSpread>> = children
+        .iter()
+        .map(|child| {
+            let expr = match child {
+                VNode::Text(text) => Expr::from(text.content.clone()),
+                VNode::Element(element) => element.automatic(ctx),
+                VNode::Fragment(fragment) => fragment.automatic(ctx),
+                VNode::Expr(expr) => Expr::clone(expr),
+                VNode::Spread(expr) => {
+                    return Some(ExprOrSpread {
+                        spread: Some(DUMMY_SP),
+                        expr: Box::new(Expr::clone(expr)),
+                    });
+                },
+            };
+
+            Some(expr.as_arg())
+        })
+        .collect();
+
+    let is_static_children = !matches!(
+        elems.as_slice(),
+        [] | [Some(ExprOrSpread { spread: None, .. })]
+    );
+
+    if is_static_children {
+        let children = ArrayLit {
+            span: DUMMY_SP,
+            elems,
+        };
+
+        props.push(Expr::from(children).into_prop("children"))
+    } else if let Some(Some(child)) = elems.pop() {
+        props.push(child.expr.into_prop("children"))
+    }
+
+    let props_expr = ObjectLit {
+        span: DUMMY_SP,
+        props,
+    };
+
+    let func = if is_static_children { "jsxs" } else { "jsx" };
+
+    let mut args = args![type_expr, props_expr];
+
+    if let Some(key) = key {
+        args.push(key.as_arg())
+    }
+
+    ctx.import_jsx_runtime(func).as_call(DUMMY_SP, args)
+}
+
+fn attr_value<C: Context>(value: &Value, ctx: &mut C) -> Expr {
+    match value {
+        Value::Lit(lit) => Lit::clone(lit).into(),
+        Value::Const(expr) | Value::Expr(expr) => Expr::clone(expr),
+        Value::Element(element) => element.automatic(ctx),
+        Value::Fragment(fragment) => fragment.automatic(ctx),
+        Value::Empty => true.into(),
+    }
+}
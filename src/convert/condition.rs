@@ -0,0 +1,116 @@
+use swc_core::{
+    common::DUMMY_SP,
+    ecma::{
+        ast::{
+            op, BinExpr, CondExpr, Expr, JSXAttr, JSXAttrName, JSXAttrOrSpread, JSXAttrValue,
+            JSXElement, JSXExpr, JSXExprContainer, Lit, Str,
+        },
+        utils::quote_ident,
+    },
+};
+
+use crate::{
+    args,
+    constant::KEY,
+    context::Context,
+    convert::list::{has_key, unwrap_paren},
+};
+
+/// ## Condition
+/// `cond ? <A /> : <B />` & `cond && <A />` children in the shape of `v-if`,
+/// branches are keyed by index, missing branches are comment placeholders
+pub fn condition<C: Context>(expr: &Expr, ctx: &mut C) -> Option<Expr> {
+    // plain logical expressions are not branches
+    if !is_condition(expr) {
+        return None;
+    }
+
+    let mut key = 0;
+
+    branch(expr, &mut key, ctx)
+}
+
+fn is_condition(expr: &Expr) -> bool {
+    matches!(
+        unwrap_paren(expr),
+        Expr::Cond(_) | Expr::Bin(BinExpr { op: op!("&&"), .. })
+    )
+}
+
+fn branch<C: Context>(expr: &Expr, key: &mut usize, ctx: &mut C) -> Option<Expr> {
+    match unwrap_paren(expr) {
+        Expr::Cond(CondExpr {
+            test, cons, alt, ..
+        }) => {
+            let cons = branch(cons, key, ctx)?;
+            let alt = branch(alt, key, ctx)?;
+
+            Some(cond_expr(Expr::clone(test), cons, alt))
+        },
+        Expr::Bin(BinExpr {
+            op: op!("&&"),
+            left,
+            right,
+            ..
+        }) => {
+            let cons = branch(right, key, ctx)?;
+
+            Some(cond_expr(Expr::clone(left), cons, comment(ctx)))
+        },
+        Expr::JSXElement(box element) => {
+            let element = with_key(element, *key);
+
+            *key += 1;
+
+            Some(Expr::JSXElement(Box::new(element)))
+        },
+        expr if is_empty(expr) => Some(comment(ctx)),
+        _ => None,
+    }
+}
+
+fn cond_expr(test: Expr, cons: Expr, alt: Expr) -> Expr {
+    CondExpr {
+        span: DUMMY_SP,
+        test: Box::new(test),
+        cons: Box::new(cons),
+        alt: Box::new(alt),
+    }
+    .into()
+}
+
+/// `createCommentVNode("v-if", true)`
+fn comment<C: Context>(ctx: &mut C) -> Expr {
+    ctx.invoke("createCommentVNode", args!["v-if", true])
+}
+
+/// `null`, `undefined`, `false` & `""` render nothing
+fn is_empty(expr: &Expr) -> bool {
+    match expr {
+        Expr::Lit(Lit::Null(_)) => true,
+        Expr::Lit(Lit::Bool(bool)) => !bool.value,
+        Expr::Lit(Lit::Str(Str { value, .. })) => value.is_empty(),
+        Expr::Ident(ident) => &*ident.sym == "undefined",
+        _ => false,
+    }
+}
+
+/// branch key, user provided key is kept
+fn with_key(element: &JSXElement, key: usize) -> JSXElement {
+    let mut element = element.clone();
+
+    if !has_key(&element) {
+        let attr = JSXAttr {
+            span: DUMMY_SP,
+            name: JSXAttrName::Ident(quote_ident!(KEY)),
+            value: Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+                span: DUMMY_SP,
+                expr: JSXExpr::Expr(Box::new(Expr::from(key as f64))),
+            })),
+        };
+
+        element.opening.attrs.push(JSXAttrOrSpread::JSXAttr(attr))
+    }
+
+    element
+}
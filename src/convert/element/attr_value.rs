@@ -1,4 +1,4 @@
-use swc_core::ecma::ast::{Expr, Lit};
+use swc_core::ecma::ast::{Expr, JSXAttr, JSXAttrOrSpread, JSXAttrValue, Lit, Str};
 use swc_helper_jsx_transform::attr::value::Value;
 
 use crate::{constant::NULL_EXPR, context::Context, convert::Convert};
@@ -14,3 +14,17 @@ impl<'a> Convert<Expr> for Value<'a> {
         }
     }
 }
+
+/// value of JSX attribute string literal, html entities are decoded by the lexer,
+/// `raw` keeps them verbatim
+pub fn jsx_str_value(raw: &JSXAttrOrSpread) -> Option<String> {
+    if let JSXAttrOrSpread::JSXAttr(JSXAttr {
+        value: Some(JSXAttrValue::Lit(Lit::Str(Str { value, .. }))),
+        ..
+    }) = raw
+    {
+        Some(value.to_string())
+    } else {
+        None
+    }
+}
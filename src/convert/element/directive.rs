@@ -20,6 +20,10 @@ impl<'a> Directive<'a> {
         let Self { name, value } = self;
 
         let directive = match name {
+            // DOM directives, custom renderer provides its own
+            V_SHOW | V_MODEL if ctx.is_custom_renderer() => {
+                ctx.resolve("resolveDirective", &name[2..])
+            },
             V_SHOW => ctx.import_from_vue("vShow").into(),
             // TODO
             V_MODEL => ctx.import_from_vue("vModelText").into(),
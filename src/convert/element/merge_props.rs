@@ -0,0 +1,77 @@
+use indexmap::IndexMap;
+use swc_core::{
+    common::util::take::Take,
+    ecma::{
+        ast::{ArrayLit, Expr, PropOrSpread},
+        utils::ExprFactory,
+    },
+};
+use swc_helper_jsx_transform::utils::is::is_event;
+
+use crate::{
+    args, array_lit,
+    constant::{CLASS, STYLE},
+    context::Context,
+    shared::expr::ExprExtend,
+    utils::normalize::{normalize_class, normalize_style, style_object},
+};
+
+fn merge_class<C: Context>(prev: Expr, next: Expr, ctx: &mut C) -> Expr {
+    let class = array_lit![prev, next].into();
+
+    match normalize_class(&class) {
+        Some(class) => class.into(),
+        None => ctx.normalize_class(args![class]),
+    }
+}
+
+fn merge_style<C: Context>(prev: Expr, next: Expr, ctx: &mut C) -> Expr {
+    let style = array_lit![prev, next].into();
+
+    match normalize_style(&style) {
+        Some(style) => style_object(style),
+        None => ctx.normalize_style(args![style]),
+    }
+}
+
+pub fn merge_listener(prev: Expr, next: Expr) -> Expr {
+    match prev {
+        Expr::Array(ArrayLit { span, mut elems }) => {
+            elems.push(Some(next.as_arg()));
+
+            ArrayLit { span, elems }.into()
+        },
+        prev => array_lit![prev, next].into(),
+    }
+}
+
+/// ## [mergeProps](https://github.com/vuejs/core/blob/main/packages/runtime-core/src/vnode.ts)
+/// merge duplicate props of one object literal at compile time,
+/// `class` is concatenated, `style` is merged and `on*` listeners are chained,
+/// the later one wins for other props
+pub fn dedupe_props<C: Context>(props: Vec<(String, Expr)>, ctx: &mut C) -> Vec<PropOrSpread> {
+    let mut prop_map: IndexMap<String, Expr> = IndexMap::with_capacity(props.len());
+
+    props.into_iter().for_each(|(name, next)| {
+        match prop_map.get_mut(&name) {
+            Some(prev) => {
+                let prev_expr = prev.take();
+
+                *prev = match name.as_str() {
+                    CLASS => merge_class(prev_expr, next, ctx),
+                    STYLE => merge_style(prev_expr, next, ctx),
+                    name if is_event(name) => merge_listener(prev_expr, next),
+                    _ => next,
+                }
+            },
+            None => {
+                prop_map.insert(name, next);
+            },
+        }
+    });
+
+    prop_map
+        .into_iter()
+        .map(|(name, expr)| expr.into_prop(&name))
+        .collect()
+}
@@ -1,43 +1,62 @@
+use indexmap::IndexSet;
 use swc_core::{
     common::{util::take::Take, DUMMY_SP},
     ecma::{
         ast::{
-            op, ArrayLit, ArrowExpr, AssignExpr, Expr, ExprOrSpread, Ident, KeyValueProp,
-            ObjectLit, Prop, PropOrSpread,
+            op, ArrayLit, ArrowExpr, AssignExpr, Expr, ExprOrSpread, Lit, ObjectLit, PropOrSpread,
+            SpreadElement,
         },
-        utils::{quote_ident, quote_str, ExprFactory},
+        utils::ExprFactory,
     },
 };
 use swc_helper_jsx_transform::{
     attr::{key::Key, Attr},
-    element::Element,
+    element::{tag::Tag, Element},
 };
 
 use crate::{
     args,
     constant::{
-        CLASS, INNER_HTML, KEY, MODEL_VALUE, NULL_EXPR, ON_CLICK, REF, STYLE, TEXT_CONTENT, V_HTML,
-        V_MODEL, V_SLOTS, V_TEXT,
+        CLASS, INNER_HTML, KEY, MODEL_VALUE, NULL_EXPR, ON_CLICK, REF, REF_FOR, REF_KEY, STYLE,
+        TEXT_CONTENT, V_DYNAMIC, V_HTML, V_MODEL, V_SLOTS, V_STATIC, V_TEXT,
     },
     context::Context,
-    convert::{element::directive::Directive, patch_flag::PatchFlag, Convert},
+    convert::{
+        element::{attr_value::jsx_str_value, directive::Directive, merge_props::dedupe_props},
+        fragment::convert_fragment_element,
+        patch_flag::PatchFlag,
+        Convert,
+    },
+    revise::has_hint,
     shared::{add::Add, expr::ExprExtend},
-    utils::is::is_directive,
+    utils::{
+        is::is_directive,
+        normalize::{
+            is_normalizable, normalize_class, normalize_style, style_object, to_handler_key,
+        },
+    },
 };
 
 pub mod attr_value;
 pub mod directive;
+pub mod merge_props;
 pub mod tag;
 
+/// props between spreads
+#[derive(Debug)]
+enum Segment {
+    Props(Vec<(String, Expr)>),
+    Spread(Expr),
+}
+
 #[derive(Debug)]
 pub struct State<'a> {
-    props: Vec<PropOrSpread>,
-    spreads: Vec<ExprOrSpread>,
+    segments: Vec<Segment>,
 
     slots: Option<Expr>,
 
     flag: isize,
-    dyn_keys: Vec<Option<ExprOrSpread>>,
+    dyn_keys: IndexSet<String>,
 
     directives: Vec<Directive<'a>>,
 
@@ -47,11 +66,10 @@ pub struct State<'a> {
 impl<'a> State<'a> {
     pub fn new(element: &'a Element) -> Self {
         Self {
-            props: Vec::new(),
-            spreads: Vec::new(),
+            segments: Vec::new(),
             slots: None,
             flag: 0,
-            dyn_keys: Vec::new(),
+            dyn_keys: IndexSet::new(),
             directives: Vec::new(),
             raw: element,
         }
@@ -86,22 +104,16 @@ impl<'a> State<'a> {
     }
 
     fn add_prop(&mut self, name: &str, expr: Expr) {
-        let key = if Ident::verify_symbol(name).is_ok() {
-            quote_ident!(name).into()
-        } else {
-            quote_str!(name).into()
-        };
-
-        let prop = Prop::KeyValue(KeyValueProp {
-            key,
-            value: Box::new(expr),
-        });
+        let prop = (name.to_string(), expr);
 
-        self.props.push(prop.into())
+        match self.segments.last_mut() {
+            Some(Segment::Props(props)) => props.push(prop),
+            _ => self.segments.push(Segment::Props(vec![prop])),
+        }
     }
 
     fn add_spread(&mut self, expr: Expr) {
-        self.spreads.add(expr)
+        self.segments.push(Segment::Spread(expr))
     }
 
     fn add_slots(&mut self, expr: Expr) {
@@ -111,7 +123,7 @@ impl<'a> State<'a> {
     fn add_dyn_key(&mut self, name: &str) {
         self.has_dyn_prop();
 
-        self.dyn_keys.push(Some(name.as_arg()))
+        self.dyn_keys.insert(name.to_string());
     }
 
     fn add_directive(&mut self, name: &'a str, expr: Expr) {
@@ -137,58 +149,114 @@ impl<'a> State<'a> {
         self.add_prop(&format!("onUpdate:{key}"), listener)
     }
 
-    fn into_expr<C: Context>(self, ctx: &mut C) -> Expr {
-        let Self {
-            props,
-            mut spreads,
-            slots,
-            flag,
-            dyn_keys,
-            directives,
-            raw:
-                Element {
-                    tag,
-                    children,
-                    is_static,
-                    ..
-                },
-            ..
-        } = self;
-
-        let tag_expr = tag.convert(ctx);
+    fn props_expr<C: Context>(segments: Vec<Segment>, ctx: &mut C) -> Expr {
+        if !ctx.is_merge_props() {
+            let props: Vec<PropOrSpread> = segments
+                .into_iter()
+                .flat_map(|segment| {
+                    match segment {
+                        Segment::Props(props) => {
+                            props
+                                .into_iter()
+                                .map(|(name, expr)| expr.into_prop(&name))
+                                .collect()
+                        },
+                        Segment::Spread(expr) => {
+                            vec![PropOrSpread::Spread(SpreadElement {
+                                dot3_token: DUMMY_SP,
+                                expr: Box::new(expr),
+                            })]
+                        },
+                    }
+                })
+                .collect();
 
-        let props_expr = if props.is_empty() {
-            if spreads.is_empty() {
+            return if props.is_empty() {
                 NULL_EXPR
             } else {
-                ctx.merge_props(spreads)
-            }
-        } else {
-            let props_obj = ObjectLit {
-                span: DUMMY_SP,
-                props,
+                ObjectLit {
+                    span: DUMMY_SP,
+                    props,
+                }
+                .into()
             };
+        }
 
-            if spreads.is_empty() {
-                props_obj.into()
-            } else {
-                spreads.add(props_obj);
+        let is_single_props = matches!(segments.as_slice(), [Segment::Props(_)]);
 
-                ctx.merge_props(spreads)
-            }
-        };
+        let mut args: Vec<ExprOrSpread> = segments
+            .into_iter()
+            .map(|segment| {
+                match segment {
+                    Segment::Props(props) => {
+                        ObjectLit {
+                            span: DUMMY_SP,
+                            props: dedupe_props(props, ctx),
+                        }
+                        .as_arg()
+                    },
+                    Segment::Spread(expr) => expr.as_arg(),
+                }
+            })
+            .collect();
+
+        if args.is_empty() {
+            NULL_EXPR
+        } else if is_single_props {
+            *args.remove(0).expr
+        } else {
+            ctx.merge_props(args)
+        }
+    }
+
+    /// `(tag, props, children or slots)`, patch flags & directives are dropped
+    pub fn into_parts<C: Context>(self, ctx: &mut C) -> (Expr, Expr, Expr) {
+        let is_dynamic = has_hint(self.raw, V_DYNAMIC);
+
+        let Self {
+            segments,
+            slots,
+            raw: Element { tag, children, .. },
+            ..
+        } = self;
+
+        let tag_expr: Expr = tag.convert(ctx);
+
+        let props_expr = Self::props_expr(segments, ctx);
 
         let children_or_slots = slots.unwrap_or_else(|| {
             if children.is_empty() {
                 NULL_EXPR
             } else {
-                children.convert(ctx)
+                let in_paragraph = ctx.is_in_paragraph();
+                let in_dynamic = ctx.is_in_dynamic();
+
+                ctx.set_in_paragraph(in_paragraph || matches!(tag, Tag::Native("p")));
+                ctx.set_in_dynamic(in_dynamic || is_dynamic);
+
+                let children_expr = children.convert(ctx);
+
+                ctx.set_in_paragraph(in_paragraph);
+                ctx.set_in_dynamic(in_dynamic);
+
+                children_expr
             }
         });
 
+        (tag_expr, props_expr, children_or_slots)
+    }
+
+    fn into_expr<C: Context>(mut self, ctx: &mut C) -> Expr {
+        let flag = self.flag;
+        let dyn_keys = std::mem::take(&mut self.dyn_keys);
+        let directives = self.directives.take();
+        let is_static = self.raw.is_static;
+
+        let (tag_expr, props_expr, children_or_slots) = self.into_parts(ctx);
+
         let mut args = args![tag_expr, props_expr, children_or_slots];
 
-        if *is_static {
+        if is_static {
             args.add(PatchFlag::HOISTED as f64);
 
             return ctx.create_element_vnode(args).hoist_to_module(ctx);
@@ -200,7 +268,7 @@ impl<'a> State<'a> {
             if !dyn_keys.is_empty() {
                 args.add(ArrayLit {
                     span: DUMMY_SP,
-                    elems: dyn_keys,
+                    elems: dyn_keys.into_iter().map(|key| Some(key.as_arg())).collect(),
                 })
             }
         }
@@ -226,143 +294,206 @@ impl<'a> State<'a> {
 
 impl<'a> Convert<Expr> for Element<'a> {
     fn convert<C: Context>(&self, ctx: &mut C) -> Expr {
-        let Self { tag, attrs, .. } = self;
+        if let Tag::Extra(ident) = self.tag && ctx.is_vue_fragment(ident) {
+            return convert_fragment_element(self, ctx);
+        }
+
+        State::analyze(self, ctx).into_expr(ctx)
+    }
+}
+
+impl<'a> State<'a> {
+    /// sort attributes into props, slots, directives & patch flags
+    pub fn analyze<C: Context>(element: &'a Element<'a>, ctx: &mut C) -> Self {
+        let Element {
+            tag, attrs, raw, ..
+        } = element;
 
         let is_cmpt = !tag.is_native();
 
-        let mut state = State::new(self);
+        let mut state = Self::new(element);
 
-        attrs.iter().for_each(|Attr { key, value }| {
-            let is_dyn = !value.is_static();
+        let raw_attrs = raw.opening.attrs.iter();
 
-            let value = value.convert(ctx);
+        attrs
+            .iter()
+            .zip(raw_attrs)
+            .for_each(|(Attr { key, value }, raw_attr)| {
+                let is_dyn = !value.is_static();
 
-            match key {
-                Key::Attr(REF) => {
-                    state.need_patch();
+                let value = match jsx_str_value(raw_attr) {
+                    Some(text) => Expr::from(text),
+                    None => value.convert(ctx),
+                };
 
-                    state.add_prop(REF, value);
-                },
-                Key::Attr(KEY) => state.add_prop(KEY, value),
-                Key::Attr(CLASS) => {
-                    if is_dyn {
-                        if is_cmpt {
-                            state.add_dyn_key(CLASS)
-                        } else {
-                            state.has_dyn_class()
-                        }
-                    }
+                match key {
+                    Key::Attr(REF) => {
+                        state.need_patch();
 
-                    state.add_prop(CLASS, value)
-                },
-                Key::Attr(STYLE) => {
-                    if is_dyn {
-                        if is_cmpt {
-                            state.add_dyn_key(STYLE)
-                        } else {
-                            state.has_dyn_style()
+                        // refs of every item are collected into an array
+                        if ctx.is_in_loop() {
+                            state.add_prop(REF_FOR, true.into())
                         }
-                    }
 
-                    state.add_prop(STYLE, value)
-                },
-
-                Key::Attr(V_TEXT) => {
-                    if is_dyn {
-                        state.add_dyn_key(TEXT_CONTENT)
-                    }
+                        // string ref also sets the setup binding
+                        let binding = match &value {
+                            Expr::Lit(Lit::Str(name)) if ctx.is_vue_at_least(3, 3) => {
+                                ctx.setup_binding(&name.value)
+                            },
+                            _ => None,
+                        };
+
+                        match binding {
+                            Some(binding) => {
+                                state.add_prop(REF_KEY, value);
+                                state.add_prop(REF, binding.into());
+                            },
+                            None => state.add_prop(REF, value),
+                        }
+                    },
+                    Key::Attr(KEY) => state.add_prop(KEY, value),
+                    Key::Attr(V_STATIC | V_DYNAMIC) => {},
+                    Key::Attr(CLASS) => {
+                        let value = match normalize_class(&value) {
+                            Some(class) => class.into(),
+                            None => {
+                                if is_dyn {
+                                    if is_cmpt {
+                                        state.add_dyn_key(CLASS)
+                                    } else {
+                                        state.has_dyn_class()
+                                    }
+                                }
+
+                                if is_normalizable(&value) {
+                                    ctx.normalize_class(args![value])
+                                } else {
+                                    value
+                                }
+                            },
+                        };
+
+                        state.add_prop(CLASS, value)
+                    },
+                    Key::Attr(STYLE) => {
+                        let value = match normalize_style(&value) {
+                            Some(style) => style_object(style),
+                            None => {
+                                if is_dyn {
+                                    if is_cmpt {
+                                        state.add_dyn_key(STYLE)
+                                    } else {
+                                        state.has_dyn_style()
+                                    }
+                                }
+
+                                if is_normalizable(&value) {
+                                    ctx.normalize_style(args![value])
+                                } else {
+                                    value
+                                }
+                            },
+                        };
+
+                        state.add_prop(STYLE, value)
+                    },
+
+                    Key::Attr(V_TEXT) => {
+                        if is_dyn {
+                            state.add_dyn_key(TEXT_CONTENT)
+                        }
 
-                    state.add_prop(TEXT_CONTENT, value);
-                },
-                Key::Attr(V_HTML) => {
-                    if is_dyn {
-                        state.add_dyn_key(INNER_HTML)
-                    }
+                        state.add_prop(TEXT_CONTENT, value);
+                    },
+                    Key::Attr(V_HTML) => {
+                        if is_dyn {
+                            state.add_dyn_key(INNER_HTML)
+                        }
 
-                    state.add_prop(INNER_HTML, value);
-                },
+                        state.add_prop(INNER_HTML, value);
+                    },
 
-                Key::Attr(V_SLOTS) => {
-                    state.has_dyn_slot();
+                    Key::Attr(V_SLOTS) => {
+                        state.has_dyn_slot();
 
-                    state.add_slots(value)
-                },
+                        state.add_slots(value)
+                    },
 
-                Key::Attr(V_MODEL) => {
-                    if is_cmpt {
-                        state.add_dyn_key(MODEL_VALUE);
+                    Key::Attr(V_MODEL) => {
+                        if is_cmpt {
+                            state.add_dyn_key(MODEL_VALUE);
 
-                        state.add_prop(MODEL_VALUE, value.clone())
-                    } else {
-                        state.need_patch();
+                            state.add_prop(MODEL_VALUE, value.clone())
+                        } else {
+                            state.need_patch();
 
-                        state.add_directive(V_MODEL, value.clone())
-                    }
+                            state.add_directive(V_MODEL, value.clone())
+                        }
 
-                    state.add_on_update(MODEL_VALUE, value, ctx)
-                },
+                        state.add_on_update(MODEL_VALUE, value, ctx)
+                    },
 
-                Key::Attr(name) if is_directive(name) => {
-                    state.need_patch();
+                    Key::Attr(name) if is_directive(name) => {
+                        state.need_patch();
 
-                    state.add_directive(name, value)
-                },
+                        state.add_directive(name, value)
+                    },
 
-                Key::Attr(name) => {
-                    if is_dyn {
-                        state.add_dyn_key(name)
-                    }
+                    Key::Attr(name) => {
+                        if is_dyn {
+                            state.add_dyn_key(name)
+                        }
 
-                    state.add_prop(name, value);
-                },
+                        state.add_prop(name, value);
+                    },
 
-                Key::Event("click" | "Click") => {
-                    if is_cmpt {
-                        state.add_dyn_key(ON_CLICK)
-                    }
+                    Key::Event("click" | "Click") => {
+                        if is_cmpt {
+                            state.add_dyn_key(ON_CLICK)
+                        }
 
-                    state.add_prop(ON_CLICK, value)
-                },
-                Key::Event(name) => {
-                    let event_name = format!("on{}{}", name[..1].to_uppercase(), &name[1..]);
+                        state.add_prop(ON_CLICK, value)
+                    },
+                    Key::Event(name) => {
+                        let event_name = to_handler_key(name);
 
-                    if is_cmpt {
-                        state.add_dyn_key(&event_name)
-                    } else {
-                        state.has_hydration_event()
-                    }
+                        if is_cmpt {
+                            state.add_dyn_key(&event_name)
+                        } else {
+                            state.has_hydration_event()
+                        }
 
-                    state.add_prop(&event_name, value);
-                },
+                        state.add_prop(&event_name, value);
+                    },
 
-                Key::NSAttr {
-                    ns: V_MODEL,
-                    name: key,
-                } => {
-                    state.add_dyn_key(key);
+                    Key::NSAttr {
+                        ns: V_MODEL,
+                        name: key,
+                    } => {
+                        state.add_dyn_key(key);
 
-                    state.add_prop(key, value.clone());
+                        state.add_prop(key, value.clone());
 
-                    state.add_on_update(key, value, ctx)
-                },
+                        state.add_on_update(key, value, ctx)
+                    },
 
-                Key::NSAttr { ns, name } => {
-                    let ns_name = format!("{ns}:{name}");
+                    Key::NSAttr { ns, name } => {
+                        let ns_name = format!("{ns}:{name}");
 
-                    if is_dyn {
-                        state.add_dyn_key(&ns_name)
-                    }
+                        if is_dyn {
+                            state.add_dyn_key(&ns_name)
+                        }
 
-                    state.add_prop(&ns_name, value);
-                },
-                Key::Spread => {
-                    state.has_full_props();
+                        state.add_prop(&ns_name, value);
+                    },
+                    Key::Spread => {
+                        state.has_full_props();
 
-                    state.add_spread(value)
-                },
-            }
-        });
+                        state.add_spread(value)
+                    },
+                }
+            });
 
-        state.into_expr(ctx)
+        state
     }
 }
@@ -1,19 +1,86 @@
-use swc_core::ecma::ast::Expr;
-use swc_helper_jsx_transform::fragment::Fragment;
+use swc_core::{
+    common::DUMMY_SP,
+    ecma::ast::{Expr, ObjectLit},
+};
+use swc_helper_jsx_transform::{
+    attr::{key::Key, Attr},
+    element::Element,
+    fragment::Fragment,
+    vnode::VNode,
+};
 
 use crate::{
     args,
-    constant::{FRAGMENT, NULL_EXPR},
+    constant::{FRAGMENT, KEY, NULL_EXPR},
     context::Context,
-    convert::Convert,
+    convert::{element::attr_value::jsx_str_value, patch_flag::PatchFlag, Convert},
+    shared::{add::Add, expr::ExprExtend},
 };
 
 impl<'a> Convert<Expr> for Fragment<'a> {
     fn convert<C: Context>(&self, ctx: &mut C) -> Expr {
-        let fragment_ident = ctx.import_from_vue(FRAGMENT);
+        fragment_vnode(None, &self.children, ctx)
+    }
+}
 
-        let children_expr = self.children.convert(ctx);
+/// explicit `<Fragment key={key}>` imported from runtime module
+pub fn convert_fragment_element<C: Context>(element: &Element, ctx: &mut C) -> Expr {
+    let Element { attrs, raw, .. } = element;
+
+    // other props are ignored by runtime
+    let key =
+        attrs
+            .iter()
+            .zip(raw.opening.attrs.iter())
+            .find_map(|(Attr { key, value }, raw_attr)| {
+                if !matches!(key, Key::Attr(KEY)) {
+                    return None;
+                }
+
+                Some(match jsx_str_value(raw_attr) {
+                    Some(text) => Expr::from(text),
+                    None => value.convert(ctx),
+                })
+            });
+
+    fragment_vnode(key, &element.children, ctx)
+}
 
-        ctx.create_vnode(args![fragment_ident, NULL_EXPR, children_expr])
+/// `createVNode(Fragment, props, children, STABLE_FRAGMENT)`,
+/// unkeyed fragment of a single element or text is flattened
+fn fragment_vnode<C: Context>(key: Option<Expr>, children: &[VNode], ctx: &mut C) -> Expr {
+    if key.is_none() && let [child] = children {
+        match child {
+            VNode::Element(element) => return element.convert(ctx),
+            VNode::Text(text) => return text.convert(ctx),
+            _ => {},
+        }
     }
+
+    let fragment_ident = ctx.import_from_vue(FRAGMENT);
+
+    let props_expr = match key {
+        Some(key) => {
+            ObjectLit {
+                span: DUMMY_SP,
+                props: vec![key.into_prop(KEY)],
+            }
+            .into()
+        },
+        None => NULL_EXPR,
+    };
+
+    let children_expr: Expr = children.convert(ctx);
+
+    let mut args = args![fragment_ident, props_expr, children_expr];
+
+    // spread children change the number of children
+    if !children
+        .iter()
+        .any(|child| matches!(child, VNode::Spread(_)))
+    {
+        args.add(PatchFlag::STABLE_FRAGMENT as f64)
+    }
+
+    ctx.create_vnode(args)
 }
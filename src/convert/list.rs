@@ -0,0 +1,219 @@
+use swc_core::{
+    common::DUMMY_SP,
+    ecma::{
+        ast::{
+            ArrowExpr, BlockStmt, BlockStmtOrExpr, CallExpr, Callee, Class, Expr, ExprOrSpread,
+            FnExpr, Function, JSXAttr, JSXAttrName, JSXAttrOrSpread, JSXElement, MemberExpr,
+            MemberProp, ParenExpr, ReturnStmt,
+        },
+        utils::{quote_ident, ExprFactory},
+        visit::{noop_visit_type, Visit, VisitWith},
+    },
+};
+
+use crate::{
+    args,
+    constant::{FRAGMENT, KEY, NULL_EXPR},
+    context::Context,
+    convert::{patch_flag::PatchFlag, Convert},
+};
+
+/// ## List
+/// `items.map(item => <li key={item.id} />)` child, rendered as keyed / unkeyed fragment
+#[derive(Debug)]
+pub struct List<'a> {
+    expr: &'a Expr,
+    pub source: &'a Expr,
+    pub callback: &'a Expr,
+    /// `None` if items are mixed keyed & unkeyed, or keys may come from spread props
+    pub is_keyed: Option<bool>,
+    /// `renderList` passes `(item, index)` only
+    pub is_render_list: bool,
+}
+
+/// key of an element rendered per item
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ItemKey {
+    Keyed,
+    Unkeyed,
+    /// `{...props}` may contain a key
+    Spread,
+}
+
+impl<'a> List<'a> {
+    pub fn analyze(expr: &'a Expr) -> Option<Self> {
+        let Expr::Call(CallExpr {
+            callee: Callee::Expr(callee),
+            args,
+            ..
+        }) = expr else {
+            return None;
+        };
+
+        let Expr::Member(MemberExpr {
+            obj,
+            prop: MemberProp::Ident(prop),
+            ..
+        }) = &**callee else {
+            return None;
+        };
+
+        let [ExprOrSpread { spread: None, expr: callback }] = args.as_slice() else {
+            return None;
+        };
+
+        if &*prop.sym != "map" {
+            return None;
+        }
+
+        let (param_count, returns) = match unwrap_paren(callback) {
+            Expr::Arrow(ArrowExpr { params, body, .. }) => {
+                let returns = match body {
+                    BlockStmtOrExpr::Expr(expr) => vec![item_key(Some(expr))],
+                    BlockStmtOrExpr::BlockStmt(block) => collect_returns(block),
+                };
+
+                (params.len(), returns)
+            },
+            Expr::Fn(FnExpr {
+                function:
+                    box Function {
+                        params,
+                        body: Some(block),
+                        ..
+                    },
+                ..
+            }) => (params.len(), collect_returns(block)),
+            _ => return None,
+        };
+
+        // every item renders a single element
+        let keys = returns.into_iter().collect::<Option<Vec<_>>>()?;
+
+        let is_keyed = match keys.first() {
+            None => return None,
+            Some(ItemKey::Spread) => None,
+            Some(first) => {
+                keys.iter()
+                    .all(|key| key == first)
+                    .then_some(*first == ItemKey::Keyed)
+            },
+        };
+
+        Some(Self {
+            expr,
+            source: obj,
+            callback,
+            is_keyed,
+            is_render_list: param_count <= 2,
+        })
+    }
+}
+
+impl<'a> Convert<Expr> for List<'a> {
+    /// `createVNode(Fragment, null, renderList(items, callback), KEYED_FRAGMENT)`
+    fn convert<C: Context>(&self, ctx: &mut C) -> Expr {
+        let Self {
+            expr,
+            source,
+            callback,
+            is_keyed,
+            is_render_list,
+        } = self;
+
+        // fragment patch flag would drop the keys present
+        let Some(is_keyed) = is_keyed else {
+            return Expr::clone(expr);
+        };
+
+        let children_expr = if *is_render_list {
+            ctx.invoke("renderList", args![
+                Expr::clone(source),
+                Expr::clone(callback)
+            ])
+        } else {
+            Expr::clone(source)
+                .make_member(quote_ident!("map"))
+                .as_call(DUMMY_SP, args![Expr::clone(callback)])
+        };
+
+        let flag = if *is_keyed {
+            PatchFlag::KEYED_FRAGMENT
+        } else {
+            PatchFlag::UN_KEYED_FRAGMENT
+        };
+
+        let fragment_ident = ctx.import_from_vue(FRAGMENT);
+
+        ctx.create_vnode(args![fragment_ident, NULL_EXPR, children_expr, flag as f64])
+    }
+}
+
+pub fn unwrap_paren(expr: &Expr) -> &Expr {
+    match expr {
+        Expr::Paren(ParenExpr { expr, .. }) => unwrap_paren(expr),
+        expr => expr,
+    }
+}
+
+pub fn has_key(element: &JSXElement) -> bool {
+    element.opening.attrs.iter().any(|attr| {
+        matches!(
+            attr,
+            JSXAttrOrSpread::JSXAttr(JSXAttr {
+                name: JSXAttrName::Ident(name),
+                ..
+            }) if &*name.sym == KEY
+        )
+    })
+}
+
+/// keys of rendered elements returned by the function body, nested functions are skipped,
+/// `None` for a return value other than an element
+#[derive(Debug, Default)]
+struct ReturnCollector(Vec<Option<ItemKey>>);
+
+impl Visit for ReturnCollector {
+    noop_visit_type!();
+
+    fn visit_return_stmt(&mut self, stmt: &ReturnStmt) {
+        self.0.push(item_key(stmt.arg.as_deref()))
+    }
+
+    fn visit_function(&mut self, _: &Function) {}
+
+    fn visit_arrow_expr(&mut self, _: &ArrowExpr) {}
+
+    fn visit_class(&mut self, _: &Class) {}
+}
+
+fn collect_returns(block: &BlockStmt) -> Vec<Option<ItemKey>> {
+    let mut collector = ReturnCollector::default();
+
+    block.visit_with(&mut collector);
+
+    collector.0
+}
+
+/// `Some(key)` if the value is an element
+fn item_key(expr: Option<&Expr>) -> Option<ItemKey> {
+    let Some(Expr::JSXElement(box element)) = expr.map(unwrap_paren) else {
+        return None;
+    };
+
+    let has_spread = element
+        .opening
+        .attrs
+        .iter()
+        .any(|attr| matches!(attr, JSXAttrOrSpread::SpreadElement(_)));
+
+    Some(
+        if has_spread {
+            ItemKey::Spread
+        } else if has_key(element) {
+            ItemKey::Keyed
+        } else {
+            ItemKey::Unkeyed
+        },
+    )
+}
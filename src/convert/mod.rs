@@ -1,11 +1,18 @@
 use crate::context::Context;
 
+pub mod automatic;
+mod condition;
 mod element;
 mod fragment;
+mod list;
 mod patch_flag;
 mod split_static;
+pub mod ssr;
+mod static_content;
 mod text;
+pub mod vapor;
 mod vnode;
+pub mod vue2;
 
 pub trait Convert<T> {
     fn convert<C: Context>(&self, ctx: &mut C) -> T;
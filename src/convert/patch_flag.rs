@@ -1,4 +1,6 @@
 /// ## [PATCH FLAG](https://github.com/vuejs/core/blob/main/packages/shared/src/patchFlags.ts)
+/// bits are stable across Vue 3 minors, renamed flags keep their values,
+/// so `vueVersion` only selects the available helpers
 #[derive(Debug)]
 pub struct PatchFlag;
 
@@ -8,7 +10,9 @@ impl PatchFlag {
     pub const CLASS: isize = 1 << 1;
     pub const DYNAMIC_SLOTS: isize = 1 << 10;
     pub const FULL_PROPS: isize = 1 << 4;
+    /// `CACHED` since 3.5
     pub const HOISTED: isize = -1;
+    /// `NEED_HYDRATION` since 3.4, also hydrates props
     pub const HYDRATE_EVENTS: isize = 1 << 5;
     pub const KEYED_FRAGMENT: isize = 1 << 7;
     pub const NEED_PATCH: isize = 1 << 9;
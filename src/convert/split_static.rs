@@ -52,12 +52,23 @@ impl<'a> SplitStatic for [VNode<'a>] {
     }
 }
 
-pub trait StaticContent {
-    fn static_content(&self) -> String;
+pub trait NodeCount {
+    /// total number of Element & Text nodes in the subtree
+    fn node_count(&self) -> usize;
 }
 
-impl<'a> StaticContent for [VNode<'a>] {
-    fn static_content(&self) -> String {
-        self.iter().map(VNode::static_content).collect()
+impl<'a> NodeCount for VNode<'a> {
+    fn node_count(&self) -> usize {
+        match self {
+            Self::Element(element) => 1 + element.children.node_count(),
+            Self::Fragment(fragment) => fragment.children.node_count(),
+            _ => 1,
+        }
+    }
+}
+
+impl<'a> NodeCount for [VNode<'a>] {
+    fn node_count(&self) -> usize {
+        self.iter().map(NodeCount::node_count).sum()
     }
 }
@@ -0,0 +1,252 @@
+use std::slice;
+
+use swc_core::{
+    common::DUMMY_SP,
+    ecma::{
+        ast::{op, BinExpr, CondExpr, Expr, Ident, ObjectLit},
+        utils::ExprFactory,
+    },
+};
+use swc_helper_jsx_transform::{
+    attr::{key::Key, Attr},
+    element::{tag::Tag, Element},
+    vnode::VNode,
+};
+
+use crate::{
+    args,
+    constant::{
+        INNER_HTML, KEY, NULL_EXPR, RAW_TEXT_ELEMENT, REF, STYLE, TEXT_CONTENT, VOID_ELEMENT,
+        V_HTML, V_MODEL, V_SHOW, V_SLOTS, V_TEXT,
+    },
+    context::Context,
+    convert::{
+        element::{attr_value::jsx_str_value, merge_props::dedupe_props, State},
+        ssr::{Part, Ssr, SsrRoot},
+        Convert,
+    },
+    shared::{expr::ExprExtend, ident::IdentExtend},
+    utils::is::is_directive,
+};
+
+/// content of element replacing children
+#[derive(Debug)]
+enum Inner {
+    /// `v-html` / `innerHTML`
+    Html(Expr),
+    /// `v-text` / `textContent`
+    Text(Expr),
+}
+
+impl<'a> Convert<Ssr> for Element<'a> {
+    fn convert<C: Context>(&self, ctx: &mut C) -> Ssr {
+        ssr_element(self, None, ctx)
+    }
+}
+
+impl<'a> SsrRoot for Element<'a> {
+    fn ssr_root<C: Context>(&self, attrs: Ident, ctx: &mut C) -> Ssr {
+        ssr_element(self, Some(attrs), ctx)
+    }
+}
+
+fn ssr_element<C: Context>(element: &Element, attrs: Option<Ident>, ctx: &mut C) -> Ssr {
+    let Element { tag, children, .. } = element;
+
+    let Tag::Native(name) = tag else {
+        return ssr_component(element, attrs, ctx);
+    };
+
+    let mut ssr = Ssr::default();
+
+    let (props_expr, inner) = ssr_props(element, attrs, ctx);
+
+    ssr.push_str("<");
+    ssr.push_str(name);
+
+    if let Some(props_expr) = props_expr {
+        let attrs_expr = ctx
+            .import_ssr_helper("ssrRenderAttrs")
+            .call(args![props_expr]);
+
+        ssr.push(Part::Interp(attrs_expr))
+    }
+
+    ssr.push_str(">");
+
+    if VOID_ELEMENT.contains(name) {
+        return ssr;
+    }
+
+    match inner {
+        Some(Inner::Html(expr)) => {
+            let html_expr = BinExpr {
+                span: DUMMY_SP,
+                op: op!("??"),
+                left: Box::new(expr),
+                right: Box::new("".into()),
+            };
+
+            ssr.push(Part::Interp(html_expr.into()))
+        },
+        Some(Inner::Text(expr)) => {
+            let text_expr = ctx.import_ssr_helper("ssrInterpolate").call(args![expr]);
+
+            ssr.push(Part::Interp(text_expr))
+        },
+        // raw text is not escaped by html parser
+        None if RAW_TEXT_ELEMENT.contains(name) => {
+            children.iter().for_each(|child| {
+                match child {
+                    VNode::Text(text) => ssr.push_str(&text.content),
+                    child => ssr.extend(slice::from_ref(child).convert(ctx)),
+                }
+            })
+        },
+        None => ssr.extend(children.convert(ctx)),
+    }
+
+    ssr.push_str("</");
+    ssr.push_str(name);
+    ssr.push_str(">");
+
+    ssr
+}
+
+/// props rendered by `ssrRenderAttrs`, refs / keys / events & directives are dropped
+fn ssr_props<C: Context>(
+    element: &Element,
+    attrs: Option<Ident>,
+    ctx: &mut C,
+) -> (Option<Expr>, Option<Inner>) {
+    let Element {
+        tag,
+        attrs: element_attrs,
+        raw,
+        ..
+    } = element;
+
+    let mut segments: Vec<Expr> = Vec::new();
+    let mut props: Vec<(String, Expr)> = Vec::new();
+    let mut inner = None;
+
+    let flush = |segments: &mut Vec<Expr>, props: &mut Vec<(String, Expr)>, ctx: &mut C| {
+        if !props.is_empty() {
+            segments.push(
+                ObjectLit {
+                    span: DUMMY_SP,
+                    props: dedupe_props(props.drain(..).collect(), ctx),
+                }
+                .into(),
+            )
+        }
+    };
+
+    element_attrs.iter().zip(raw.opening.attrs.iter()).for_each(
+        |(Attr { key, value }, raw_attr)| {
+            let value = match jsx_str_value(raw_attr) {
+                Some(text) => Expr::from(text),
+                None => value.convert(ctx),
+            };
+
+            match key {
+                Key::Attr(REF | KEY | V_SLOTS) | Key::Event(_) => {},
+
+                Key::Attr(V_HTML | INNER_HTML) => inner = Some(Inner::Html(value)),
+                Key::Attr(V_TEXT | TEXT_CONTENT) => inner = Some(Inner::Text(value)),
+
+                Key::Attr(V_SHOW) => {
+                    let display = ObjectLit {
+                        span: DUMMY_SP,
+                        props: vec![Expr::from("none").into_prop("display")],
+                    };
+
+                    let style = CondExpr {
+                        span: DUMMY_SP,
+                        test: Box::new(value),
+                        cons: Box::new(NULL_EXPR),
+                        alt: Box::new(display.into()),
+                    };
+
+                    props.push((STYLE.to_string(), style.into()))
+                },
+
+                // text input renders value, other elements keep the value unset
+                Key::Attr(V_MODEL) => {
+                    if let Tag::Native("input") = tag {
+                        props.push(("value".to_string(), value))
+                    } else if let Tag::Native("textarea") = tag {
+                        inner = Some(Inner::Text(value))
+                    }
+                },
+
+                // custom directives have no server side effect
+                Key::Attr(name) if is_directive(name) => {},
+                Key::NSAttr { ns, .. } if is_directive(ns) => {},
+
+                Key::Attr(name) => props.push((name.to_string(), value)),
+                Key::NSAttr { ns, name } => props.push((format!("{ns}:{name}"), value)),
+
+                Key::Spread => {
+                    flush(&mut segments, &mut props, ctx);
+
+                    segments.push(value)
+                },
+            }
+        },
+    );
+
+    flush(&mut segments, &mut props, ctx);
+
+    if let Some(attrs) = attrs {
+        segments.push(attrs.into())
+    }
+
+    let props_expr = match segments.len() {
+        0 => None,
+        1 => segments.pop(),
+        _ => {
+            let args = segments.into_iter().map(|expr| expr.as_arg()).collect();
+
+            Some(ctx.merge_props(args))
+        },
+    };
+
+    (props_expr, inner)
+}
+
+/// `_push(ssrRenderComponent(tag, props, children, _parent))`
+fn ssr_component<C: Context>(element: &Element, attrs: Option<Ident>, ctx: &mut C) -> Ssr {
+    let (tag_expr, mut props_expr, children_expr) = State::analyze(element, ctx).into_parts(ctx);
+
+    if let Some(attrs) = attrs {
+        let merge_props = ctx.import_from_vue("mergeProps");
+
+        props_expr = match props_expr {
+            NULL_EXPR => attrs.into(),
+            Expr::Call(mut call)
+                if call
+                    .callee
+                    .as_expr()
+                    .and_then(|callee| callee.as_ident())
+                    .map_or(false, |callee| callee.to_id() == merge_props.to_id()) =>
+            {
+                call.args.push(attrs.as_arg());
+
+                call.into()
+            },
+            props_expr => ctx.merge_props(args![props_expr, attrs]),
+        }
+    }
+
+    let parent = ctx.get_ident("_parent");
+
+    let component_expr = ctx.import_ssr_helper("ssrRenderComponent").call(args![
+        tag_expr,
+        props_expr,
+        children_expr,
+        parent
+    ]);
+
+    Ssr(vec![Part::Push(component_expr)])
+}
@@ -0,0 +1,333 @@
+use std::{mem, slice};
+
+use swc_core::{
+    common::{util::take::Take, DUMMY_SP},
+    ecma::{
+        ast::{
+            ArrowExpr, BlockStmt, BlockStmtOrExpr, Class, Expr, FnExpr, Function, Ident,
+            JSXElement, ReturnStmt, Stmt, Tpl, TplElement,
+        },
+        utils::{quote_ident, ExprFactory},
+        visit::{noop_visit_mut_type, VisitMut, VisitMutWith},
+    },
+};
+use swc_helper_jsx_transform::{fragment::Fragment, shared::Transform, text::Text, vnode::VNode};
+
+use crate::{
+    args,
+    context::Context,
+    convert::{
+        list::{unwrap_paren, List},
+        static_content::StaticContent,
+        Convert,
+    },
+    revise::Revise,
+    shared::ident::IdentExtend,
+    utils::{html::escape_html, jsx::JSXNormalizer},
+};
+
+mod element;
+
+/// fragment anchors, same as client vnode fragment
+const FRAGMENT_START: &str = "<!--[-->";
+const FRAGMENT_END: &str = "<!--]-->";
+
+#[derive(Debug)]
+pub enum Part {
+    /// html content
+    Str(String),
+    /// string expression interpolated into html content
+    Interp(Expr),
+    /// buffer pushed separately, e.g. `ssrRenderComponent`
+    Push(Expr),
+    Stmt(Stmt),
+}
+
+/// ## SSR backend
+/// string building code pushed to `_push` buffer
+#[derive(Debug, Default)]
+pub struct Ssr(pub Vec<Part>);
+
+impl Ssr {
+    fn push_str(&mut self, text: &str) {
+        match self.0.last_mut() {
+            Some(Part::Str(buf)) => buf.push_str(text),
+            _ => self.0.push(Part::Str(text.to_string())),
+        }
+    }
+
+    fn push(&mut self, part: Part) {
+        match part {
+            Part::Str(text) => self.push_str(&text),
+            part => self.0.push(part),
+        }
+    }
+
+    fn extend(&mut self, Ssr(parts): Ssr) {
+        parts.into_iter().for_each(|part| self.push(part))
+    }
+
+    /// merge html content & interpolations into template literal pushes
+    pub fn into_stmts(self, push: &Ident) -> Vec<Stmt> {
+        let mut stmts = Vec::new();
+
+        let mut tpl = Tpl {
+            span: DUMMY_SP,
+            exprs: Vec::new(),
+            quasis: Vec::new(),
+        };
+
+        let mut quasi = String::new();
+
+        let flush = |stmts: &mut Vec<Stmt>, tpl: &mut Tpl, quasi: &mut String| {
+            if tpl.exprs.is_empty() && quasi.is_empty() {
+                return;
+            }
+
+            tpl.quasis.push(tpl_element(&mem::take(quasi), true));
+
+            let content = mem::replace(tpl, Tpl {
+                span: DUMMY_SP,
+                exprs: Vec::new(),
+                quasis: Vec::new(),
+            });
+
+            stmts.push(push.clone().call(args![content]).into_stmt())
+        };
+
+        self.0.into_iter().for_each(|part| {
+            match part {
+                Part::Str(text) => quasi.push_str(&text),
+                Part::Interp(expr) => {
+                    tpl.quasis.push(tpl_element(&mem::take(&mut quasi), false));
+                    tpl.exprs.push(Box::new(expr))
+                },
+                Part::Push(expr) => {
+                    flush(&mut stmts, &mut tpl, &mut quasi);
+
+                    stmts.push(push.clone().call(args![expr]).into_stmt())
+                },
+                Part::Stmt(stmt) => {
+                    flush(&mut stmts, &mut tpl, &mut quasi);
+
+                    stmts.push(stmt)
+                },
+            }
+        });
+
+        flush(&mut stmts, &mut tpl, &mut quasi);
+
+        stmts
+    }
+}
+
+fn tpl_element(text: &str, tail: bool) -> TplElement {
+    let raw = text
+        .replace('\\', "\\\\")
+        .replace('`', "\\`")
+        .replace("${", "$\\{");
+
+    TplElement {
+        span: DUMMY_SP,
+        tail,
+        cooked: Some(text.into()),
+        raw: raw.into(),
+    }
+}
+
+/// ## SSR root
+/// returned by render function, fallthrough `_attrs` are merged into the root element
+pub trait SsrRoot {
+    fn ssr_root<C: Context>(&self, attrs: Ident, ctx: &mut C) -> Ssr;
+}
+
+impl<'a> SsrRoot for Fragment<'a> {
+    fn ssr_root<C: Context>(&self, _: Ident, ctx: &mut C) -> Ssr {
+        self.convert(ctx)
+    }
+}
+
+impl<'a> Convert<Ssr> for Text<'a> {
+    fn convert<C: Context>(&self, _: &mut C) -> Ssr {
+        Ssr(vec![Part::Str(escape_html(&self.content))])
+    }
+}
+
+impl<'a> Convert<Ssr> for Fragment<'a> {
+    fn convert<C: Context>(&self, ctx: &mut C) -> Ssr {
+        let mut ssr = Ssr::default();
+
+        ssr.push_str(FRAGMENT_START);
+        ssr.extend(self.children.convert(ctx));
+        ssr.push_str(FRAGMENT_END);
+
+        ssr
+    }
+}
+
+impl<'a> Convert<Ssr> for [VNode<'a>] {
+    fn convert<C: Context>(&self, ctx: &mut C) -> Ssr {
+        let mut ssr = Ssr::default();
+
+        self.iter().for_each(|vnode| {
+            // static subtree is serialized at compile time, no client vnodes to count on hydration
+            if vnode.is_static() && let Some((content, _)) = slice::from_ref(vnode).static_content(false) {
+                return ssr.push_str(&content);
+            }
+
+            let part = match vnode {
+                VNode::Text(text) => text.convert(ctx),
+                VNode::Element(element) => element.convert(ctx),
+                VNode::Fragment(fragment) => fragment.convert(ctx),
+                VNode::Expr(expr) => render_expr(Expr::clone(expr), ctx),
+                VNode::Spread(expr) => Ssr(vec![render_spread(Expr::clone(expr), ctx)]),
+            };
+
+            ssr.extend(part)
+        });
+
+        ssr
+    }
+}
+
+/// `{expr}` child may be string or vnode, rendered as the client does
+fn render_expr<C: Context>(expr: Expr, ctx: &mut C) -> Ssr {
+    if let Some(list) = List::analyze(&expr) {
+        return render_list(&list, ctx);
+    }
+
+    let part = match expr {
+        Expr::Lit(_) | Expr::Tpl(_) => {
+            Part::Interp(ctx.import_ssr_helper("ssrInterpolate").call(args![expr]))
+        },
+        expr => Part::Stmt(render_vnode(expr, ctx).into_stmt()),
+    };
+
+    Ssr(vec![part])
+}
+
+/// `items.map(item => <li />)` child, items are pushed in a fragment,
+/// `ssrRenderList(items, item => { _push(`<li></li>`) })`
+fn render_list<C: Context>(list: &List, ctx: &mut C) -> Ssr {
+    let mut callback = unwrap_paren(list.callback).clone();
+
+    callback.visit_mut_with(&mut JSXNormalizer);
+
+    match &mut callback {
+        Expr::Arrow(ArrowExpr { body, .. }) => {
+            match body {
+                BlockStmtOrExpr::Expr(expr) => {
+                    if let Expr::JSXElement(element) = unwrap_paren(expr) {
+                        let stmts = render_item(element, ctx);
+
+                        *body = BlockStmtOrExpr::BlockStmt(BlockStmt {
+                            span: DUMMY_SP,
+                            stmts,
+                        })
+                    }
+                },
+                BlockStmtOrExpr::BlockStmt(block) => block.visit_mut_with(&mut ItemRenderer(ctx)),
+            }
+        },
+        Expr::Fn(FnExpr {
+            function: box Function {
+                body: Some(block), ..
+            },
+            ..
+        }) => block.visit_mut_with(&mut ItemRenderer(ctx)),
+        _ => {},
+    }
+
+    let render_expr = if list.is_render_list {
+        ctx.import_ssr_helper("ssrRenderList")
+            .call(args![Expr::clone(list.source), callback])
+    } else {
+        Expr::clone(list.source)
+            .make_member(quote_ident!("forEach"))
+            .as_call(DUMMY_SP, args![callback])
+    };
+
+    Ssr(vec![
+        Part::Str(FRAGMENT_START.into()),
+        Part::Stmt(render_expr.into_stmt()),
+        Part::Str(FRAGMENT_END.into()),
+    ])
+}
+
+/// push statements of an element rendered per item
+fn render_item<C: Context>(element: &JSXElement, ctx: &mut C) -> Vec<Stmt> {
+    let mut ir = element.transform();
+
+    ir.revise(ctx);
+
+    let push = ctx.get_ident("_push");
+    let ssr: Ssr = ir.convert(ctx);
+
+    ssr.into_stmts(&push)
+}
+
+/// `return <li />` in callback body to pushes & `return`, nested functions are skipped
+struct ItemRenderer<'c, C: Context>(&'c mut C);
+
+impl<'c, C: Context> VisitMut for ItemRenderer<'c, C> {
+    noop_visit_mut_type!();
+
+    fn visit_mut_stmt(&mut self, stmt: &mut Stmt) {
+        let Stmt::Return(ReturnStmt { arg: Some(arg), .. }) = stmt else {
+            return stmt.visit_mut_children_with(self);
+        };
+
+        let Expr::JSXElement(element) = unwrap_paren(arg) else {
+            return;
+        };
+
+        let mut stmts = render_item(element, self.0);
+
+        stmts.push(Stmt::Return(ReturnStmt {
+            span: DUMMY_SP,
+            arg: None,
+        }));
+
+        *stmt = Stmt::Block(BlockStmt {
+            span: DUMMY_SP,
+            stmts,
+        })
+    }
+
+    fn visit_mut_function(&mut self, _: &mut Function) {}
+
+    fn visit_mut_arrow_expr(&mut self, _: &mut ArrowExpr) {}
+
+    fn visit_mut_class(&mut self, _: &mut Class) {}
+}
+
+/// `{...exprs}` children are rendered one by one
+fn render_spread<C: Context>(expr: Expr, ctx: &mut C) -> Part {
+    let item = ctx.get_ident("$v");
+
+    let callback = ArrowExpr {
+        params: vec![item.clone().into()],
+        body: render_vnode(item.into(), ctx).into(),
+        ..Take::dummy()
+    };
+
+    Part::Stmt(
+        expr.make_member(quote_ident!("forEach"))
+            .as_call(DUMMY_SP, args![callback])
+            .into_stmt(),
+    )
+}
+
+/// `ssrRenderVNode(_push, ssrUtils.normalizeVNode(expr), _parent)`
+fn render_vnode<C: Context>(expr: Expr, ctx: &mut C) -> Expr {
+    let normalized = ctx
+        .import_from_vue("ssrUtils")
+        .make_member(quote_ident!("normalizeVNode"))
+        .as_call(DUMMY_SP, args![expr]);
+
+    let push = ctx.get_ident("_push");
+    let parent = ctx.get_ident("_parent");
+
+    ctx.import_ssr_helper("ssrRenderVNode")
+        .call(args![push, normalized, parent])
+}
@@ -0,0 +1,251 @@
+use indexmap::IndexSet;
+use swc_core::ecma::ast::{Bool, Expr, JSXAttrOrSpread, Lit, Number, Str};
+use swc_helper_jsx_transform::{
+    attr::{key::Key, value::Value, Attr},
+    element::{tag::Tag, Element},
+    text::Text,
+    utils::is::is_bool_attr,
+    vnode::VNode,
+};
+
+use crate::{
+    constant::{
+        CLASS, CLOSE_P_ELEMENT, DOM_PROP, KEY, NON_STRINGIFIABLE_ELEMENT, RAW_TEXT_ELEMENT, REF,
+        STYLE, VOID_ELEMENT, V_DYNAMIC, V_STATIC,
+    },
+    convert::element::attr_value::jsx_str_value,
+    utils::{
+        html::{escape_html, stringify_number},
+        is::{is_attr_name, is_directive},
+        normalize::{normalize_class, normalize_style, stringify_style},
+    },
+};
+
+/// ## Static Content
+/// serialize static vnodes to html & top-level DOM node count for `createStaticVNode`,
+/// `None` if the vnodes can not be stringified safely,
+/// `in_paragraph` if the vnodes are children of a `<p>` ancestor
+pub trait StaticContent {
+    fn static_content(&self, in_paragraph: bool) -> Option<(String, usize)>;
+}
+
+impl<'a> StaticContent for [VNode<'a>] {
+    fn static_content(&self, in_paragraph: bool) -> Option<(String, usize)> {
+        // html parser would drop these nodes, hydration node count mismatch
+        if self.iter().any(is_non_stringifiable) {
+            return None;
+        }
+
+        let mut html = Html {
+            in_paragraph,
+            ..Default::default()
+        };
+
+        html.write_vnodes(self)?;
+
+        Some((html.buf, html.node_count))
+    }
+}
+
+/// serialize a static attribute, `Some("")` if the attribute is omitted
+pub fn static_attr(attr: &Attr, raw_attr: &JSXAttrOrSpread) -> Option<String> {
+    let mut html = Html::default();
+
+    html.write_attr(attr, raw_attr)?;
+
+    Some(html.buf)
+}
+
+fn is_non_stringifiable(vnode: &VNode) -> bool {
+    if let VNode::Element(element) = vnode && let Tag::Native(name) = element.tag {
+        NON_STRINGIFIABLE_ELEMENT.contains(name)
+    } else {
+        false
+    }
+}
+
+#[derive(Debug, Default)]
+struct Html {
+    buf: String,
+    in_paragraph: bool,
+    /// element nesting depth of the writing position
+    depth: usize,
+    /// top-level DOM nodes written
+    node_count: usize,
+    /// the last top-level node is text, html parser merges adjacent texts into one node
+    after_text: bool,
+}
+
+impl Html {
+    fn write_vnodes(&mut self, vnodes: &[VNode]) -> Option<()> {
+        vnodes.iter().try_for_each(|vnode| self.write_vnode(vnode))
+    }
+
+    fn write_vnode(&mut self, vnode: &VNode) -> Option<()> {
+        match vnode {
+            VNode::Text(box Text { content, .. }) => {
+                if self.depth == 0 && !content.is_empty() && !self.after_text {
+                    self.node_count += 1;
+                    self.after_text = true
+                }
+
+                self.buf.push_str(&escape_html(content));
+
+                Some(())
+            },
+            VNode::Element(element) if element.is_static => self.write_element(element),
+            VNode::Fragment(fragment) if fragment.is_static => {
+                self.write_vnodes(&fragment.children)
+            },
+            _ => None,
+        }
+    }
+
+    fn write_element(&mut self, element: &Element) -> Option<()> {
+        let Element {
+            tag,
+            attrs,
+            children,
+            raw,
+            ..
+        } = element;
+
+        let Tag::Native(name) = tag else {
+            return None;
+        };
+
+        if self.depth == 0 {
+            self.node_count += 1;
+            self.after_text = false
+        }
+
+        // html parser would auto close <p>, hydration node count mismatch
+        if self.in_paragraph && CLOSE_P_ELEMENT.contains(name) {
+            return None;
+        }
+
+        self.buf.push('<');
+        self.buf.push_str(name);
+
+        let mut attr_names = IndexSet::new();
+
+        attrs
+            .iter()
+            .zip(raw.opening.attrs.iter())
+            .try_for_each(|(attr, raw_attr)| {
+                if let Key::Attr(V_STATIC | V_DYNAMIC) = attr.key {
+                    return Some(());
+                }
+
+                let name = self.write_attr(attr, raw_attr)?;
+
+                // html parser keeps the first one of duplicate attributes
+                attr_names.insert(name).then_some(())
+            })?;
+
+        self.buf.push('>');
+
+        if VOID_ELEMENT.contains(name) {
+            return children.is_empty().then_some(());
+        }
+
+        if RAW_TEXT_ELEMENT.contains(name) && !children.is_empty() {
+            return None;
+        }
+
+        let in_paragraph = self.in_paragraph;
+
+        self.in_paragraph = in_paragraph || *name == "p";
+        self.depth += 1;
+
+        self.write_vnodes(children)?;
+
+        self.in_paragraph = in_paragraph;
+        self.depth -= 1;
+
+        self.buf.push_str("</");
+        self.buf.push_str(name);
+        self.buf.push('>');
+
+        Some(())
+    }
+
+    /// write attribute, return the attribute name
+    fn write_attr(&mut self, attr: &Attr, raw_attr: &JSXAttrOrSpread) -> Option<String> {
+        let Attr { key, value } = attr;
+
+        let name = match key {
+            Key::Attr(REF | KEY) => return None,
+            Key::Attr(name) if is_directive(name) || DOM_PROP.contains(name) => return None,
+            Key::Attr(name) => name.to_string(),
+            Key::NSAttr { ns, .. } if is_directive(ns) => return None,
+            Key::NSAttr { ns, name } => format!("{ns}:{name}"),
+            Key::Event(_) | Key::Spread => return None,
+        };
+
+        if !is_attr_name(&name) {
+            return None;
+        }
+
+        if let Key::Attr(CLASS | STYLE) = key && let Some(content) = normalized_content(key, value, raw_attr) {
+            if !content.is_empty() {
+                self.write_attr_content(&name, Some(content))
+            }
+
+            return Some(name);
+        }
+
+        let Value::Lit(lit) = value else {
+            return None;
+        };
+
+        let is_bool = is_bool_attr(&name);
+
+        let content = match lit {
+            Lit::Null(_) => return Some(name),
+            Lit::Bool(Bool { value: false, .. }) if is_bool => return Some(name),
+            Lit::Bool(Bool { value: true, .. }) if is_bool => None,
+            Lit::Bool(Bool { value, .. }) => Some(value.to_string()),
+            Lit::Str(Str { value, .. }) => {
+                Some(jsx_str_value(raw_attr).unwrap_or_else(|| value.to_string()))
+            },
+            Lit::Num(Number { value, .. }) => Some(stringify_number(*value)),
+            _ => return None,
+        };
+
+        self.write_attr_content(&name, content);
+
+        Some(name)
+    }
+
+    fn write_attr_content(&mut self, name: &str, content: Option<String>) {
+        self.buf.push(' ');
+        self.buf.push_str(name);
+
+        if let Some(content) = content {
+            self.buf.push_str("=\"");
+            self.buf.push_str(&escape_html(&content));
+            self.buf.push('"');
+        }
+    }
+}
+
+/// normalized `class` & `style` content of constant value
+fn normalized_content(key: &Key, value: &Value, raw_attr: &JSXAttrOrSpread) -> Option<String> {
+    let expr = match jsx_str_value(raw_attr) {
+        Some(text) => Expr::from(text),
+        None => {
+            match value {
+                Value::Lit(lit) => Lit::clone(lit).into(),
+                Value::Const(expr) => Expr::clone(expr),
+                _ => return None,
+            }
+        },
+    };
+
+    match key {
+        Key::Attr(CLASS) => normalize_class(&expr),
+        Key::Attr(STYLE) => normalize_style(&expr).map(|style| stringify_style(&style)),
+        _ => None,
+    }
+}
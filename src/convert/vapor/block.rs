@@ -0,0 +1,368 @@
+use std::collections::HashMap;
+
+use swc_core::{
+    common::{util::take::Take, DUMMY_SP},
+    ecma::{
+        ast::{op, ArrayLit, ArrowExpr, AssignExpr, Expr, Ident, ObjectLit, PropOrSpread},
+        utils::ExprFactory,
+    },
+};
+use swc_helper_jsx_transform::{
+    attr::{key::Key, Attr},
+    element::Element,
+    vnode::VNode,
+};
+
+use crate::{
+    args, array_lit,
+    constant::{CLASS, KEY, MODEL_VALUE, NULL_EXPR, REF, STYLE, V_MODEL, V_SLOTS},
+    context::Context,
+    convert::{
+        element::attr_value::jsx_str_value,
+        vapor::{
+            convert_value,
+            template::{has_jsx, Binding, Template},
+            Block,
+        },
+        Convert,
+    },
+    shared::expr::ExprExtend,
+    utils::{is::is_directive, normalize::to_handler_key},
+};
+
+impl Block {
+    /// native element from template, component from `createComponent`
+    pub(super) fn element<C: Context>(&mut self, element: &Element, ctx: &mut C) -> Ident {
+        if !element.tag.is_native() {
+            return self.component(element, ctx);
+        }
+
+        let mut template = Template::default();
+
+        template.write_element(element, &mut Vec::new(), ctx);
+
+        let Template { html, bindings } = template;
+
+        let factory = ctx.invoke("template", args![html]).hoist_to_module(ctx);
+
+        let root = self.decl(factory.as_call(DUMMY_SP, Vec::new()));
+
+        let mut nodes = HashMap::from([(Vec::new(), root.clone())]);
+
+        // nodes are located before insertion changes child indexes
+        bindings.iter().for_each(|(path, _)| {
+            self.node(path, &root, &mut nodes, ctx);
+        });
+
+        bindings.into_iter().for_each(|(path, binding)| {
+            let node = nodes[&path].clone();
+
+            self.bind(
+                node,
+                binding,
+                || nodes[&path[..path.len() - 1]].clone(),
+                ctx,
+            )
+        });
+
+        root
+    }
+
+    /// `child(parent)` for the first child, `nthChild(parent, idx)` otherwise
+    fn node<C: Context>(
+        &mut self,
+        path: &[usize],
+        root: &Ident,
+        nodes: &mut HashMap<Vec<usize>, Ident>,
+        ctx: &mut C,
+    ) -> Ident {
+        let Some((idx, parent_path)) = path.split_last() else {
+            return root.clone();
+        };
+
+        if let Some(node) = nodes.get(path) {
+            return node.clone();
+        }
+
+        let parent = self.node(parent_path, root, nodes, ctx);
+
+        let init = match idx {
+            0 => ctx.invoke("child", args![parent]),
+            idx => ctx.invoke("nthChild", args![parent, *idx as f64]),
+        };
+
+        let node = self.decl(init);
+
+        nodes.insert(path.to_vec(), node.clone());
+
+        node
+    }
+
+    fn bind<C: Context>(
+        &mut self,
+        node: Ident,
+        binding: Binding,
+        parent: impl FnOnce() -> Ident,
+        ctx: &mut C,
+    ) {
+        match binding {
+            Binding::Prop(name, value) => {
+                let expr = match name.as_str() {
+                    CLASS => ctx.invoke("setClass", args![node, value]),
+                    STYLE => ctx.invoke("setStyle", args![node, value]),
+                    name => ctx.invoke("setProp", args![node, name, value]),
+                };
+
+                self.effect(expr)
+            },
+            Binding::Spread(value) => {
+                let expr = ctx.invoke("setDynamicProps", args![node, array_lit![value]]);
+
+                self.effect(expr)
+            },
+            Binding::Text(parts) => {
+                let mut args = args![node];
+
+                args.extend(parts.into_iter().map(|part| part.as_arg()));
+
+                let expr = ctx.invoke("setText", args);
+
+                self.effect(expr)
+            },
+            Binding::Html(value) => {
+                let expr = ctx.invoke("setHtml", args![node, value]);
+
+                self.effect(expr)
+            },
+            Binding::Event(name, value) => {
+                let expr = ctx.invoke("on", args![node, name, value]);
+
+                self.stmt(expr)
+            },
+            Binding::Show(value) => {
+                let expr = ctx.invoke("applyVShow", args![node, getter(value)]);
+
+                self.stmt(expr)
+            },
+            Binding::Model(value) => {
+                let setter = assign_listener(value.clone(), ctx);
+
+                let expr = ctx.invoke("applyTextModel", args![node, getter(value), setter]);
+
+                self.stmt(expr)
+            },
+            Binding::Directive(name, value) => {
+                let directive = ctx.resolve("resolveDirective", name);
+
+                let expr = ctx.invoke("withVaporDirectives", args![node, array_lit![array_lit![
+                    directive,
+                    getter(value)
+                ]]]);
+
+                self.stmt(expr)
+            },
+            Binding::Ref(value) => {
+                let expr = ctx
+                    .invoke("createTemplateRefSetter", Vec::new())
+                    .as_call(DUMMY_SP, args![node, value]);
+
+                self.stmt(expr)
+            },
+            Binding::Insert(value) => {
+                let expr = ctx.invoke("insert", args![value, parent(), node]);
+
+                self.stmt(expr)
+            },
+            Binding::Component(element) => {
+                let component = self.component(element, ctx);
+
+                let expr = ctx.invoke("insert", args![component, parent(), node]);
+
+                self.stmt(expr)
+            },
+        }
+    }
+
+    /// `createComponent(tag, { prop: () => value }, { default: () => block })`
+    fn component<C: Context>(&mut self, element: &Element, ctx: &mut C) -> Ident {
+        let Element {
+            tag,
+            attrs,
+            children,
+            raw,
+            ..
+        } = element;
+
+        let tag_expr: Expr = tag.convert(ctx);
+
+        let mut props: Vec<PropOrSpread> = Vec::new();
+        let mut spreads: Vec<Expr> = Vec::new();
+        let mut slots = None;
+        let mut refs = Vec::new();
+
+        attrs
+            .iter()
+            .zip(raw.opening.attrs.iter())
+            .for_each(|(Attr { key, value }, raw_attr)| {
+                let value = match jsx_str_value(raw_attr) {
+                    Some(text) => Expr::from(text),
+                    None => convert_value(value, ctx),
+                };
+
+                match key {
+                    Key::Attr(KEY) => {},
+                    Key::Attr(REF) => refs.push(value),
+                    Key::Attr(V_SLOTS) => slots = Some(value),
+                    Key::Attr(V_MODEL) => {
+                        let listener = getter(assign_listener(value.clone(), ctx));
+
+                        props.push(getter(value).into_prop(MODEL_VALUE));
+                        props.push(listener.into_prop(&format!("onUpdate:{MODEL_VALUE}")));
+                    },
+                    Key::NSAttr { ns: V_MODEL, name } => {
+                        let listener = getter(assign_listener(value.clone(), ctx));
+
+                        props.push(getter(value).into_prop(name));
+                        props.push(listener.into_prop(&format!("onUpdate:{name}")));
+                    },
+                    // directives on components are not supported by vapor runtime
+                    Key::Attr(name) if is_directive(name) => {},
+                    Key::NSAttr { ns, .. } if is_directive(ns) => {},
+                    Key::Attr(name) => props.push(getter(value).into_prop(name)),
+                    Key::NSAttr { ns, name } => {
+                        props.push(getter(value).into_prop(&format!("{ns}:{name}")))
+                    },
+                    Key::Event(name) => props.push(getter(value).into_prop(&to_handler_key(name))),
+                    Key::Spread => spreads.push(getter(value)),
+                }
+            });
+
+        // dynamic props are resolved from `$` sources
+        if !spreads.is_empty() {
+            let sources = spreads
+                .into_iter()
+                .map(|expr| Some(expr.as_arg()))
+                .collect();
+
+            props.push(
+                Expr::from(ArrayLit {
+                    span: DUMMY_SP,
+                    elems: sources,
+                })
+                .into_prop("$"),
+            );
+        }
+
+        let props_expr = if props.is_empty() {
+            NULL_EXPR
+        } else {
+            object(props)
+        };
+
+        let slots_expr = if children.is_empty() {
+            slots.unwrap_or(NULL_EXPR)
+        } else {
+            let mut block = Block::default();
+
+            let nodes = block.children(children, ctx);
+
+            object(vec![
+                Expr::from(block.into_fn(nodes, ctx)).into_prop("default"),
+            ])
+        };
+
+        let init = ctx.invoke("createComponent", args![tag_expr, props_expr, slots_expr]);
+
+        let component = self.decl(init);
+
+        refs.into_iter().for_each(|value| {
+            let expr = ctx
+                .invoke("createTemplateRefSetter", Vec::new())
+                .as_call(DUMMY_SP, args![component.clone(), value]);
+
+            self.stmt(expr)
+        });
+
+        component
+    }
+
+    /// blocks of children, an array if there are multiple
+    pub(super) fn children<C: Context>(&mut self, children: &[VNode], ctx: &mut C) -> Expr {
+        let mut nodes = Vec::new();
+
+        self.write_children(children, &mut nodes, ctx);
+
+        match nodes.len() {
+            1 => nodes.remove(0),
+            _ => {
+                ArrayLit {
+                    span: DUMMY_SP,
+                    elems: nodes.into_iter().map(|node| Some(node.as_arg())).collect(),
+                }
+                .into()
+            },
+        }
+    }
+
+    fn write_children<C: Context>(
+        &mut self,
+        children: &[VNode],
+        nodes: &mut Vec<Expr>,
+        ctx: &mut C,
+    ) {
+        children.iter().for_each(|child| {
+            match child {
+                VNode::Element(element) => nodes.push(self.element(element, ctx).into()),
+                VNode::Fragment(fragment) => self.write_children(&fragment.children, nodes, ctx),
+                VNode::Text(text) => {
+                    let node = self.decl(ctx.invoke("createTextNode", args![text.content.clone()]));
+
+                    nodes.push(node.into())
+                },
+                VNode::Expr(expr) if !has_jsx(expr) => {
+                    let node = self.decl(ctx.invoke("createTextNode", Vec::new()));
+
+                    self.effect(ctx.invoke("setText", args![node.clone(), Expr::clone(expr)]));
+
+                    nodes.push(node.into())
+                },
+                VNode::Expr(expr) | VNode::Spread(expr) => nodes.push(Expr::clone(expr)),
+            }
+        })
+    }
+}
+
+/// `() => value`
+fn getter(value: Expr) -> Expr {
+    ArrowExpr {
+        body: Box::new(value).into(),
+        ..Take::dummy()
+    }
+    .into()
+}
+
+/// `$v => target = $v`
+fn assign_listener<C: Context>(target: Expr, ctx: &mut C) -> Expr {
+    let param = ctx.get_ident("$v");
+
+    ArrowExpr {
+        params: vec![param.clone().into()],
+        body: AssignExpr {
+            span: DUMMY_SP,
+            left: target.as_pat_or_expr(),
+            op: op!("="),
+            right: param.into(),
+        }
+        .into(),
+        ..Take::dummy()
+    }
+    .into()
+}
+
+fn object(props: Vec<PropOrSpread>) -> Expr {
+    ObjectLit {
+        span: DUMMY_SP,
+        props,
+    }
+    .into()
+}
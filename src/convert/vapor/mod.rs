@@ -0,0 +1,122 @@
+use swc_core::{
+    common::{util::take::Take, DUMMY_SP},
+    ecma::{
+        ast::{ArrowExpr, BlockStmt, Expr, Ident, Stmt, VarDecl, VarDeclKind, VarDeclarator},
+        utils::{private_ident, ExprFactory},
+    },
+};
+use swc_helper_jsx_transform::{attr::value::Value, element::Element, fragment::Fragment};
+
+use crate::{context::Context, convert::Convert};
+
+mod block;
+mod template;
+
+/// ## Vapor backend
+/// experimental, DOM nodes are created by `template()` factories,
+/// dynamic bindings are updated by `renderEffect` without vnodes
+#[derive(Debug)]
+pub struct Vapor(pub Expr);
+
+#[derive(Debug, Default)]
+struct Block {
+    stmts: Vec<Stmt>,
+    effects: Vec<Stmt>,
+}
+
+impl Block {
+    fn decl(&mut self, init: Expr) -> Ident {
+        let ident = private_ident!("n");
+
+        let decl = VarDecl {
+            kind: VarDeclKind::Const,
+            decls: vec![VarDeclarator {
+                name: ident.clone().into(),
+                init: Some(Box::new(init)),
+                ..Take::dummy()
+            }],
+            ..Take::dummy()
+        };
+
+        self.stmts.push(decl.into());
+
+        ident
+    }
+
+    fn stmt(&mut self, expr: Expr) {
+        self.stmts.push(expr.into_stmt())
+    }
+
+    fn effect(&mut self, expr: Expr) {
+        self.effects.push(expr.into_stmt())
+    }
+
+    /// `() => { ...; renderEffect(() => { ... }); return block }`
+    fn into_fn<C: Context>(self, block: Expr, ctx: &mut C) -> ArrowExpr {
+        let Self { mut stmts, effects } = self;
+
+        if !effects.is_empty() {
+            let effect_fn = ArrowExpr {
+                body: BlockStmt {
+                    span: DUMMY_SP,
+                    stmts: effects,
+                }
+                .into(),
+                ..Take::dummy()
+            };
+
+            stmts.push(
+                ctx.invoke("renderEffect", vec![effect_fn.as_arg()])
+                    .into_stmt(),
+            )
+        }
+
+        stmts.push(block.into_return_stmt().into());
+
+        ArrowExpr {
+            body: BlockStmt {
+                span: DUMMY_SP,
+                stmts,
+            }
+            .into(),
+            ..Take::dummy()
+        }
+    }
+}
+
+impl<'a> Convert<Vapor> for Element<'a> {
+    fn convert<C: Context>(&self, ctx: &mut C) -> Vapor {
+        let mut block = Block::default();
+
+        let node = block.element(self, ctx);
+
+        Vapor(block.into_fn(node.into(), ctx).as_iife().into())
+    }
+}
+
+impl<'a> Convert<Vapor> for Fragment<'a> {
+    fn convert<C: Context>(&self, ctx: &mut C) -> Vapor {
+        let mut block = Block::default();
+
+        let nodes = block.children(&self.children, ctx);
+
+        Vapor(block.into_fn(nodes, ctx).as_iife().into())
+    }
+}
+
+/// attribute value, nested JSX is compiled to vapor blocks
+fn convert_value<C: Context>(value: &Value, ctx: &mut C) -> Expr {
+    match value {
+        Value::Element(element) => {
+            let Vapor(expr) = element.convert(ctx);
+
+            expr
+        },
+        Value::Fragment(fragment) => {
+            let Vapor(expr) = fragment.convert(ctx);
+
+            expr
+        },
+        value => value.convert(ctx),
+    }
+}
@@ -0,0 +1,282 @@
+use swc_core::ecma::{
+    ast::{Expr, JSXElement, JSXFragment},
+    visit::{noop_visit_type, Visit, VisitWith},
+};
+use swc_helper_jsx_transform::{
+    attr::{key::Key, Attr},
+    element::{tag::Tag, Element},
+    vnode::VNode,
+};
+
+use crate::{
+    constant::{
+        INNER_HTML, KEY, RAW_TEXT_ELEMENT, REF, TEXT_CONTENT, VOID_ELEMENT, V_DYNAMIC, V_HTML,
+        V_MODEL, V_SHOW, V_SLOTS, V_STATIC, V_TEXT,
+    },
+    context::Context,
+    convert::{
+        element::attr_value::jsx_str_value, static_content::static_attr, vapor::convert_value,
+    },
+    utils::{html::escape_html, is::is_directive, normalize::uncapitalize},
+};
+
+/// adjacent text child, merged into one text node
+#[derive(Debug, Clone, Copy)]
+enum TextPart<'a> {
+    Static(&'a str),
+    Dynamic(&'a Expr),
+}
+
+/// dynamic binding of template node
+#[derive(Debug)]
+pub enum Binding<'a> {
+    Prop(String, Expr),
+    Spread(Expr),
+    Event(String, Expr),
+    /// text node, static parts are string literals
+    Text(Vec<Expr>),
+    Html(Expr),
+    Show(Expr),
+    Model(Expr),
+    Directive(&'a str, Expr),
+    Ref(Expr),
+    /// block inserted before the anchor node
+    Insert(Expr),
+    /// component inserted before the anchor node
+    Component(&'a Element<'a>),
+}
+
+/// html of `template()` factory, bindings are addressed by child index path
+#[derive(Debug, Default)]
+pub struct Template<'a> {
+    pub html: String,
+    pub bindings: Vec<(Vec<usize>, Binding<'a>)>,
+}
+
+impl<'a> Template<'a> {
+    fn bind(&mut self, path: &[usize], binding: Binding<'a>) {
+        self.bindings.push((path.to_vec(), binding))
+    }
+
+    pub fn write_element<C: Context>(
+        &mut self,
+        element: &'a Element<'a>,
+        path: &mut Vec<usize>,
+        ctx: &mut C,
+    ) {
+        let Element {
+            tag,
+            attrs,
+            children,
+            raw,
+            ..
+        } = element;
+
+        let Tag::Native(name) = tag else {
+            return;
+        };
+
+        self.html.push('<');
+        self.html.push_str(name);
+
+        let mut has_inner = false;
+
+        attrs
+            .iter()
+            .zip(raw.opening.attrs.iter())
+            .for_each(|(attr, raw_attr)| {
+                let Attr { key, value } = attr;
+
+                let value = match jsx_str_value(raw_attr) {
+                    Some(text) => Expr::from(text),
+                    None => convert_value(value, ctx),
+                };
+
+                let binding = match key {
+                    Key::Attr(KEY | V_SLOTS | V_STATIC | V_DYNAMIC) => return,
+                    Key::Attr(REF) => Binding::Ref(value),
+                    Key::Attr(V_SHOW) => Binding::Show(value),
+                    Key::Attr(V_MODEL) => Binding::Model(value),
+                    Key::Attr(V_HTML | INNER_HTML) => {
+                        has_inner = true;
+
+                        Binding::Html(value)
+                    },
+                    Key::Attr(V_TEXT | TEXT_CONTENT) => {
+                        has_inner = true;
+
+                        Binding::Text(vec![value])
+                    },
+                    Key::Attr(name) if is_directive(name) => Binding::Directive(&name[2..], value),
+                    // `v-model:arg` only works with components
+                    Key::NSAttr { ns, .. } if is_directive(ns) => return,
+                    Key::Event(name) => {
+                        let event_name = uncapitalize(name);
+
+                        Binding::Event(event_name, value)
+                    },
+                    Key::Spread => Binding::Spread(value),
+                    Key::Attr(name) | Key::NSAttr { name, .. } => {
+                        if let Some(content) = static_attr(attr, raw_attr) {
+                            return self.html.push_str(&content);
+                        }
+
+                        let name = match key {
+                            Key::NSAttr { ns, .. } => format!("{ns}:{name}"),
+                            _ => name.to_string(),
+                        };
+
+                        Binding::Prop(name, value)
+                    },
+                };
+
+                self.bind(path, binding)
+            });
+
+        self.html.push('>');
+
+        if VOID_ELEMENT.contains(name) {
+            return;
+        }
+
+        if !has_inner {
+            let mut idx = 0;
+
+            let is_raw_text = RAW_TEXT_ELEMENT.contains(name);
+
+            self.write_children(children, path, &mut idx, is_raw_text, ctx);
+        }
+
+        self.html.push_str("</");
+        self.html.push_str(name);
+        self.html.push('>');
+    }
+
+    fn write_children<C: Context>(
+        &mut self,
+        children: &'a [VNode<'a>],
+        path: &mut Vec<usize>,
+        idx: &mut usize,
+        is_raw_text: bool,
+        ctx: &mut C,
+    ) {
+        let mut texts = Vec::new();
+
+        children.iter().for_each(|child| {
+            match child {
+                VNode::Text(text) => return texts.push(TextPart::Static(&text.content)),
+                VNode::Expr(expr) if !has_jsx(expr) => return texts.push(TextPart::Dynamic(expr)),
+                _ => {},
+            }
+
+            self.write_texts(&mut texts, path, idx, is_raw_text);
+
+            match child {
+                VNode::Element(element) if element.tag.is_native() => {
+                    path.push(*idx);
+
+                    self.write_element(element, path, ctx);
+
+                    path.pop();
+                },
+                VNode::Element(element) => {
+                    self.write_anchor(path, idx, Binding::Component(element))
+                },
+                VNode::Expr(expr) | VNode::Spread(expr) => {
+                    self.write_anchor(path, idx, Binding::Insert(Expr::clone(expr)))
+                },
+                VNode::Fragment(fragment) => {
+                    return self.write_children(&fragment.children, path, idx, is_raw_text, ctx);
+                },
+                VNode::Text(_) => {},
+            }
+
+            *idx += 1;
+        });
+
+        self.write_texts(&mut texts, path, idx, is_raw_text)
+    }
+
+    /// adjacent texts are merged into one text node by html parser
+    fn write_texts(
+        &mut self,
+        texts: &mut Vec<TextPart>,
+        path: &mut Vec<usize>,
+        idx: &mut usize,
+        is_raw_text: bool,
+    ) {
+        if texts.is_empty() {
+            return;
+        }
+
+        let is_static = texts.iter().all(|text| matches!(text, TextPart::Static(_)));
+
+        if is_static {
+            texts.drain(..).for_each(|text| {
+                if let TextPart::Static(content) = text {
+                    if is_raw_text {
+                        self.html.push_str(content)
+                    } else {
+                        self.html.push_str(&escape_html(content))
+                    }
+                }
+            })
+        } else {
+            let parts = texts
+                .drain(..)
+                .map(|text| {
+                    match text {
+                        TextPart::Static(content) => Expr::from(content.to_string()),
+                        TextPart::Dynamic(expr) => Expr::clone(expr),
+                    }
+                })
+                .collect();
+
+            // placeholder text node
+            self.html.push(' ');
+
+            path.push(*idx);
+
+            self.bind(path, Binding::Text(parts));
+
+            path.pop();
+        }
+
+        *idx += 1;
+    }
+
+    /// `<!>` comment node as insertion anchor
+    fn write_anchor(&mut self, path: &mut Vec<usize>, idx: &usize, binding: Binding<'a>) {
+        self.html.push_str("<!>");
+
+        path.push(*idx);
+
+        self.bind(path, binding);
+
+        path.pop();
+    }
+}
+
+#[derive(Debug, Default)]
+struct JSXFinder(bool);
+
+impl Visit for JSXFinder {
+    noop_visit_type!();
+
+    fn visit_jsx_element(&mut self, _: &JSXElement) {
+        self.0 = true
+    }
+
+    fn visit_jsx_fragment(&mut self, _: &JSXFragment) {
+        self.0 = true
+    }
+}
+
+/// expression child which may render nodes rather than text
+pub fn has_jsx(expr: &Expr) -> bool {
+    let mut finder = JSXFinder::default();
+
+    expr.visit_with(&mut finder);
+
+    finder.0
+}
@@ -8,7 +8,10 @@ use crate::{
     args,
     context::Context,
     convert::{
-        split_static::{Block, SplitStatic, StaticContent},
+        condition::condition,
+        list::List,
+        split_static::{Block, NodeCount, SplitStatic},
+        static_content::StaticContent,
         Convert,
     },
     shared::add::Add,
@@ -17,16 +20,38 @@ use crate::{
 impl<'a> Convert<ExprOrSpread> for VNode<'a> {
     fn convert<C: Context>(&self, ctx: &mut C) -> ExprOrSpread {
         match self {
-            Self::Text(text) => text.convert(ctx).into(),
-            Self::Element(element) => element.convert(ctx).into(),
-            Self::Expr(expr) => Expr::clone(expr).into(),
+            Self::Text(text) => {
+                let expr: Expr = text.convert(ctx);
+
+                expr.into()
+            },
+            Self::Element(element) => {
+                let expr: Expr = element.convert(ctx);
+
+                expr.into()
+            },
+            Self::Expr(expr) => {
+                if let Some(list) = List::analyze(expr) {
+                    let expr: Expr = list.convert(ctx);
+
+                    return expr.into();
+                }
+
+                condition(expr, ctx)
+                    .unwrap_or_else(|| Expr::clone(expr))
+                    .into()
+            },
             Self::Spread(expr) => {
                 ExprOrSpread {
                     spread: Some(DUMMY_SP),
                     expr: Box::new(Expr::clone(expr)),
                 }
             },
-            Self::Fragment(fragment) => fragment.convert(ctx).into(),
+            Self::Fragment(fragment) => {
+                let expr: Expr = fragment.convert(ctx);
+
+                expr.into()
+            },
         }
     }
 }
@@ -41,18 +66,27 @@ impl<'a> Convert<Expr> for [VNode<'a>] {
             match block {
                 Block::VNode(vnode) => elems.add(vnode.convert(ctx)),
                 Block::Static(statics) => {
-                    let num = statics.len();
-
-                    if num < threshold {
-                        statics.iter().for_each(|vnode| {
-                            elems.add(vnode.convert(ctx));
-                        })
+                    // html content only works with DOM renderer
+                    let content = if ctx.is_custom_renderer() || statics.node_count() < threshold {
+                        None
                     } else {
-                        let content = statics.static_content();
+                        statics
+                            .static_content(ctx.is_in_paragraph())
+                            .filter(|(content, _)| content.len() <= ctx.static_size_limit())
+                    };
 
-                        let static_vnode_expr = ctx.create_static_vnode(args![content, num]);
+                    match content {
+                        Some((content, node_count)) => {
+                            let static_vnode_expr =
+                                ctx.create_static_vnode(args![content, node_count]);
 
-                        elems.add(static_vnode_expr.into())
+                            elems.add(static_vnode_expr.into())
+                        },
+                        None => {
+                            statics.iter().for_each(|vnode| {
+                                elems.add(vnode.convert(ctx));
+                            })
+                        },
                     }
                 },
             }
@@ -0,0 +1,292 @@
+use swc_core::{
+    common::{util::take::Take, DUMMY_SP},
+    ecma::{
+        ast::{op, ArrayLit, ArrowExpr, AssignExpr, Expr, ObjectLit, PropOrSpread},
+        utils::{quote_ident, quote_str, ExprFactory},
+    },
+    quote_expr,
+};
+use swc_helper_jsx_transform::{
+    attr::{key::Key, value::Value, Attr},
+    element::{tag::Tag, Element},
+};
+
+use crate::{
+    args,
+    constant::{
+        CLASS, DOM_PROP, INNER_HTML, KEY, NULL_EXPR, REF, STYLE, TEXT_CONTENT, V_DYNAMIC, V_HTML,
+        V_MODEL, V_SLOTS, V_STATIC, V_TEXT,
+    },
+    context::Context,
+    convert::{element::attr_value::jsx_str_value, vue2::H, Convert},
+    shared::{expr::ExprExtend, ident::IdentExtend},
+    utils::{is::is_directive, normalize::uncapitalize},
+};
+
+/// ## [Vue 2 data object](https://v2.vuejs.org/v2/guide/render-function.html#The-Data-Object-In-Depth)
+#[derive(Debug, Default)]
+struct Data {
+    spreads: Vec<Expr>,
+    /// `class`, `style`, `key`, `ref`, `model`
+    top: Vec<(String, Expr)>,
+    attrs: Vec<(String, Expr)>,
+    dom_props: Vec<(String, Expr)>,
+    on: Vec<(String, Expr)>,
+    directives: Vec<Expr>,
+    scoped_slots: Option<Expr>,
+}
+
+impl Data {
+    fn add_directive(&mut self, name: &str, value: Expr) {
+        let props = vec![Expr::from(name).into_prop("name"), value.into_prop("value")];
+
+        self.directives.push(object(props))
+    }
+
+    fn into_expr<C: Context>(self, ctx: &mut C) -> Expr {
+        let Self {
+            spreads,
+            top,
+            attrs,
+            dom_props,
+            on,
+            directives,
+            scoped_slots,
+        } = self;
+
+        let mut props: Vec<PropOrSpread> = top
+            .into_iter()
+            .map(|(name, expr)| expr.into_prop(&name))
+            .collect();
+
+        [("attrs", attrs), ("domProps", dom_props), ("on", on)]
+            .into_iter()
+            .filter(|(_, group)| !group.is_empty())
+            .for_each(|(key, group)| {
+                let group = group
+                    .into_iter()
+                    .map(|(name, expr)| expr.into_prop(&name))
+                    .collect();
+
+                props.push(object(group).into_prop(key))
+            });
+
+        if !directives.is_empty() {
+            let directives = ArrayLit {
+                span: DUMMY_SP,
+                elems: directives
+                    .into_iter()
+                    .map(|expr| Some(expr.as_arg()))
+                    .collect(),
+            };
+
+            props.push(Expr::from(directives).into_prop("directives"))
+        }
+
+        if let Some(slots) = scoped_slots {
+            props.push(slots.into_prop("scopedSlots"))
+        }
+
+        if spreads.is_empty() {
+            return if props.is_empty() {
+                NULL_EXPR
+            } else {
+                object(props)
+            };
+        }
+
+        // spread data objects are deep merged, then the compiled keys
+        let merge_data = ctx.hoist_to_module(merge_data_fn());
+
+        let args = spreads
+            .into_iter()
+            .chain((!props.is_empty()).then(|| object(props)))
+            .map(|expr| expr.as_arg())
+            .collect();
+
+        merge_data.as_call(DUMMY_SP, args)
+    }
+}
+
+/// merge Vue 2 data objects like `babel-helper-vue-jsx-merge-props`,
+/// `class` / `style` / `directives` are concatenated, `attrs` / `props` / `domProps` are merged,
+/// listeners of `on` / `nativeOn` are collected into arrays, others are overridden
+fn merge_data_fn() -> Expr {
+    *quote_expr!(
+        r#"(...objs) => objs.reduce((data, obj) => {
+            for (const key in obj) {
+                const prev = data[key], next = obj[key];
+
+                data[key] = prev === undefined ? next
+                    : /^(class|style|directives)$/.test(key) ? [].concat(prev, next)
+                    : /^(attrs|props|domProps)$/.test(key) ? { ...prev, ...next }
+                    : /^(on|nativeOn)$/.test(key) ? Object.keys(next).reduce((on, name) => {
+                        on[name] = on[name] ? [].concat(on[name], next[name]) : next[name];
+
+                        return on;
+                    }, { ...prev })
+                    : next;
+            }
+
+            return data;
+        }, {})"#
+    )
+}
+
+fn object(props: Vec<PropOrSpread>) -> Expr {
+    ObjectLit {
+        span: DUMMY_SP,
+        props,
+    }
+    .into()
+}
+
+/// `$v => target = $v`
+fn assign_listener<C: Context>(target: Expr, value: Expr, ctx: &mut C) -> Expr {
+    ArrowExpr {
+        params: vec![ctx.get_ident("$v").into()],
+        body: AssignExpr {
+            span: DUMMY_SP,
+            left: target.as_pat_or_expr(),
+            op: op!("="),
+            right: Box::new(value),
+        }
+        .into(),
+        ..Take::dummy()
+    }
+    .into()
+}
+
+fn convert_value<C: Context>(value: &Value, ctx: &mut C) -> Expr {
+    match value {
+        Value::Element(element) => {
+            let H(expr) = element.convert(ctx);
+
+            expr
+        },
+        Value::Fragment(fragment) => {
+            let H(expr) = fragment.convert(ctx);
+
+            expr
+        },
+        value => value.convert(ctx),
+    }
+}
+
+impl<'a> Convert<H> for Tag<'a> {
+    fn convert<C: Context>(&self, ctx: &mut C) -> H {
+        let expr = match self {
+            // Vue 2 resolves registered components by name at runtime
+            Self::Extra(ident) if ctx.is_unresolved(ident) => quote_str!(ident.sym.clone()).into(),
+            tag => tag.convert(ctx),
+        };
+
+        H(expr)
+    }
+}
+
+impl<'a> Convert<H> for Element<'a> {
+    fn convert<C: Context>(&self, ctx: &mut C) -> H {
+        let Self {
+            tag,
+            attrs,
+            children,
+            raw,
+            ..
+        } = self;
+
+        let is_cmpt = !tag.is_native();
+
+        let mut data = Data::default();
+
+        attrs
+            .iter()
+            .zip(raw.opening.attrs.iter())
+            .for_each(|(Attr { key, value }, raw_attr)| {
+                let value = match jsx_str_value(raw_attr) {
+                    Some(text) => Expr::from(text),
+                    None => convert_value(value, ctx),
+                };
+
+                match key {
+                    Key::Attr(V_STATIC | V_DYNAMIC) => {},
+                    Key::Attr(name @ (REF | KEY | CLASS | STYLE)) => {
+                        data.top.push((name.to_string(), value))
+                    },
+
+                    Key::Attr(V_TEXT) => data.dom_props.push((TEXT_CONTENT.to_string(), value)),
+                    Key::Attr(V_HTML) => data.dom_props.push((INNER_HTML.to_string(), value)),
+
+                    Key::Attr(V_SLOTS) => data.scoped_slots = Some(value),
+
+                    Key::Attr(V_MODEL) => {
+                        if is_cmpt {
+                            let callback =
+                                assign_listener(value.clone(), ctx.get_ident("$v").into(), ctx);
+
+                            let model = object(vec![
+                                value.into_prop("value"),
+                                callback.into_prop("callback"),
+                            ]);
+
+                            data.top.push(("model".to_string(), model))
+                        } else {
+                            let target = ctx
+                                .get_ident("$v")
+                                .make_member(quote_ident!("target"))
+                                .make_member(quote_ident!("value"));
+
+                            let listener = assign_listener(value.clone(), target, ctx);
+
+                            data.dom_props.push(("value".to_string(), value));
+
+                            data.on.push(("input".to_string(), listener))
+                        }
+                    },
+
+                    Key::Attr(name) if is_directive(name) => data.add_directive(&name[2..], value),
+
+                    Key::Attr(name) if DOM_PROP.contains(name) => {
+                        data.dom_props.push((name.to_string(), value))
+                    },
+
+                    Key::Attr(name) => data.attrs.push((name.to_string(), value)),
+
+                    Key::Event(name) => {
+                        let event_name = uncapitalize(name);
+
+                        data.on.push((event_name, value))
+                    },
+
+                    // `.sync` modifier of Vue 2
+                    Key::NSAttr {
+                        ns: V_MODEL,
+                        name: key,
+                    } => {
+                        let listener =
+                            assign_listener(value.clone(), ctx.get_ident("$v").into(), ctx);
+
+                        data.attrs.push((key.to_string(), value));
+
+                        data.on.push((format!("update:{key}"), listener))
+                    },
+
+                    Key::NSAttr { ns, name } => data.attrs.push((format!("{ns}:{name}"), value)),
+
+                    Key::Spread => data.spreads.push(value),
+                }
+            });
+
+        let H(tag_expr) = tag.convert(ctx);
+
+        let mut args = args![tag_expr, data.into_expr(ctx)];
+
+        if !children.is_empty() {
+            let H(children_expr) = children.convert(ctx);
+
+            args.push(children_expr.as_arg())
+        }
+
+        H(ctx.import_from_vue("h").call(args))
+    }
+}
@@ -0,0 +1,64 @@
+use swc_core::{
+    common::DUMMY_SP,
+    ecma::{
+        ast::{ArrayLit, Expr, ExprOrSpread},
+        utils::ExprFactory,
+    },
+};
+use swc_helper_jsx_transform::{fragment::Fragment, vnode::VNode};
+
+use crate::{context::Context, convert::Convert, shared::add::Add};
+
+mod element;
+
+/// ## Vue 2.7 backend
+/// render function expression composed of `h(tag, data, children)`
+#[derive(Debug)]
+pub struct H(pub Expr);
+
+impl<'a> Convert<H> for Fragment<'a> {
+    /// Vue 2 has no fragment, children are rendered as array
+    fn convert<C: Context>(&self, ctx: &mut C) -> H {
+        self.children.convert(ctx)
+    }
+}
+
+impl<'a> Convert<H> for [VNode<'a>] {
+    fn convert<C: Context>(&self, ctx: &mut C) -> H {
+        let mut elems = Vec::with_capacity(self.len());
+
+        self.iter()
+            .for_each(|vnode| add_vnode(&mut elems, vnode, ctx));
+
+        H(ArrayLit {
+            span: DUMMY_SP,
+            elems,
+        }
+        .into())
+    }
+}
+
+fn add_vnode<C: Context>(elems: &mut Vec<Option<ExprOrSpread>>, vnode: &VNode, ctx: &mut C) {
+    match vnode {
+        VNode::Text(text) => elems.add(Expr::from(text.content.clone()).as_arg()),
+        VNode::Element(element) => {
+            let H(expr) = element.convert(ctx);
+
+            elems.add(expr.as_arg())
+        },
+        VNode::Expr(expr) => elems.add(Expr::clone(expr).as_arg()),
+        VNode::Spread(expr) => {
+            elems.add(ExprOrSpread {
+                spread: Some(DUMMY_SP),
+                expr: Box::new(Expr::clone(expr)),
+            })
+        },
+        // flatten fragment into parent children
+        VNode::Fragment(fragment) => {
+            fragment
+                .children
+                .iter()
+                .for_each(|vnode| add_vnode(elems, vnode, ctx))
+        },
+    }
+}
@@ -0,0 +1,103 @@
+use indexmap::IndexMap;
+use swc_core::{
+    common::{util::take::Take, DUMMY_SP},
+    ecma::{
+        ast::{
+            Ident, ImportDecl, ImportNamedSpecifier, ImportSpecifier, Module, ModuleDecl,
+            ModuleExportName, ModuleItem,
+        },
+        utils::{private_ident, quote_str},
+    },
+};
+
+/// ## Import Helper
+/// import helpers in module programs, reuse named imports written by the user, e.g.
+/// `import { createVNode } from "vue"`
+#[derive(Debug, Default)]
+pub struct ImportHelper {
+    /// named imports of the module, `path -> imported name -> local`
+    imported_map: IndexMap<String, IndexMap<String, Ident>>,
+    added_map: IndexMap<String, IndexMap<&'static str, Ident>>,
+    /// position after the leading imports
+    add_index: usize,
+}
+
+impl ImportHelper {
+    pub fn store(&mut self, module: &Module) {
+        for item in &module.body {
+            let ModuleItem::ModuleDecl(ModuleDecl::Import(import)) = item else {
+                return;
+            };
+
+            self.add_index += 1;
+
+            if import.type_only {
+                continue;
+            }
+
+            let named_map = self
+                .imported_map
+                .entry(import.src.value.to_string())
+                .or_default();
+
+            for spec in &import.specifiers {
+                if let ImportSpecifier::Named(ImportNamedSpecifier {
+                    local,
+                    imported,
+                    is_type_only: false,
+                    ..
+                }) = spec
+                {
+                    let name = match imported {
+                        Some(ModuleExportName::Ident(ident)) => &ident.sym,
+                        Some(ModuleExportName::Str(str)) => &str.value,
+                        None => &local.sym,
+                    };
+
+                    named_map.insert(name.to_string(), local.clone());
+                }
+            }
+        }
+    }
+
+    pub fn get_or_import(&mut self, name: &'static str, path: &str) -> &Ident {
+        if let Some(ident) = self
+            .imported_map
+            .get(path)
+            .and_then(|named_map| named_map.get(name))
+        {
+            return ident;
+        }
+
+        self.added_map
+            .entry(path.to_string())
+            .or_default()
+            .entry(name)
+            .or_insert_with(|| private_ident!(name))
+    }
+
+    /// insert import declarations after the leading imports
+    pub fn add_to_module(&mut self, module: &mut Module) {
+        let items = self.added_map.drain(..).map(|(path, ident_map)| {
+            let specifiers = ident_map
+                .into_values()
+                .map(|local| {
+                    ImportSpecifier::Named(ImportNamedSpecifier {
+                        span: DUMMY_SP,
+                        local,
+                        imported: None,
+                        is_type_only: false,
+                    })
+                })
+                .collect();
+
+            ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+                specifiers,
+                src: Box::new(quote_str!(path)),
+                ..Take::dummy()
+            }))
+        });
+
+        module.body.splice(self.add_index..self.add_index, items);
+    }
+}
@@ -1,12 +1,19 @@
 use indexmap::IndexMap;
 use swc_core::{
-    common::util::take::Take,
-    ecma::ast::{Expr, Ident, Module, ModuleDecl, ModuleItem, VarDecl, VarDeclKind},
+    common::{util::take::Take, DUMMY_SP},
+    ecma::{
+        ast::{BlockStmt, BlockStmtOrExpr, Expr, Ident, VarDecl, VarDeclKind},
+        utils::ExprFactory,
+    },
 };
 
-use crate::hoist::decl_map::Declarator;
+use crate::{hoist::decl_map::Declarator, utils::is::is_directive_stmt};
 
 mod decl_map;
+pub mod import;
+pub mod module;
+pub mod require;
+pub mod scope;
 
 #[derive(Debug)]
 pub struct Hoist<'a> {
@@ -42,20 +49,33 @@ impl<'a> Hoist<'a> {
         }
     }
 
-    pub fn add_to_module(&mut self, module: &mut Module) {
+    pub fn add_to_block(&mut self, block: &mut BlockStmt) {
         if let Some(decl) = self.get_var_decl() {
-            let Module { body, .. } = module;
+            let BlockStmt { stmts, .. } = block;
 
-            let mut idx = 0;
+            // keep directive prologue in front
+            let idx = stmts
+                .iter()
+                .take_while(|stmt| is_directive_stmt(stmt))
+                .count();
 
-            for item in body.iter() {
-                match item {
-                    ModuleItem::ModuleDecl(ModuleDecl::Import(_)) => idx += 1,
-                    _ => break,
-                }
-            }
+            stmts.insert(idx, decl.into())
+        }
+    }
 
-            body.insert(idx, decl.into())
+    pub fn add_to_fn_body(&mut self, body: &mut BlockStmtOrExpr) {
+        match body {
+            BlockStmtOrExpr::BlockStmt(block) => self.add_to_block(block),
+            BlockStmtOrExpr::Expr(expr) => {
+                if let Some(decl) = self.get_var_decl() {
+                    let expr = *expr.take();
+
+                    *body = BlockStmtOrExpr::BlockStmt(BlockStmt {
+                        span: DUMMY_SP,
+                        stmts: vec![decl.into(), expr.into_return_stmt().into()],
+                    })
+                }
+            },
         }
     }
 }
@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+
+use indexmap::IndexMap;
+use swc_core::{
+    common::util::take::Take,
+    ecma::{
+        ast::{
+            ClassDecl, Decl, DefaultDecl, ExportDecl, ExportDefaultDecl, Expr, Id, Ident,
+            ImportDecl, Module, ModuleDecl, ModuleItem, PropName, Stmt, VarDecl, VarDeclKind,
+        },
+        utils::find_pat_ids,
+        visit::{noop_visit_type, visit_obj_and_computed, Visit, VisitWith},
+    },
+};
+
+use crate::hoist::decl_map::Declarator;
+
+/// module level binding
+#[derive(Debug, Clone, Copy)]
+struct Binding {
+    /// index of module item which declares the binding
+    idx: usize,
+    /// binding is in temporal dead zone before its declaration
+    tdz: bool,
+}
+
+/// collect identifiers referenced by hoisted expression
+#[derive(Debug, Default)]
+struct RefCollector {
+    refs: Vec<Id>,
+}
+
+impl Visit for RefCollector {
+    noop_visit_type!();
+
+    visit_obj_and_computed!();
+
+    fn visit_ident(&mut self, ident: &Ident) {
+        self.refs.push(ident.to_id())
+    }
+
+    fn visit_prop_name(&mut self, name: &PropName) {
+        if let PropName::Computed(computed) = name {
+            computed.visit_with(self)
+        }
+    }
+}
+
+/// ## Module Hoist
+/// place each hoisted declaration after the module bindings it references,
+/// and before its first usage evaluated at module load time
+#[derive(Debug)]
+pub struct ModuleHoist<'a> {
+    name: &'a str,
+    decl_map: IndexMap<Expr, Ident>,
+
+    bindings: HashMap<Id, Binding>,
+    /// index of module item to insert the hoisted declaration before
+    positions: HashMap<Id, usize>,
+    /// index after leading imports
+    start: usize,
+}
+
+impl<'a> ModuleHoist<'a> {
+    pub fn new(name: &'a str) -> Self {
+        Self {
+            name,
+            decl_map: IndexMap::new(),
+            bindings: HashMap::new(),
+            positions: HashMap::new(),
+            start: 0,
+        }
+    }
+
+    pub fn store(&mut self, module: &Module) {
+        let Self {
+            bindings, start, ..
+        } = self;
+
+        *start = module
+            .body
+            .iter()
+            .take_while(|item| matches!(item, ModuleItem::ModuleDecl(ModuleDecl::Import(_))))
+            .count();
+
+        for (idx, item) in module.body.iter().enumerate() {
+            let (ids, tdz): (Vec<Id>, bool) = match item {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl { specifiers, .. })) => {
+                    (find_pat_ids(specifiers), false)
+                },
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { decl, .. }))
+                | ModuleItem::Stmt(Stmt::Decl(decl)) => {
+                    match decl {
+                        Decl::Fn(decl) => (vec![decl.ident.to_id()], false),
+                        Decl::Class(ClassDecl { ident, .. }) => (vec![ident.to_id()], true),
+                        Decl::Var(box VarDecl { decls, .. }) => (find_pat_ids(decls), true),
+                        _ => continue,
+                    }
+                },
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                    decl: DefaultDecl::Class(class),
+                    ..
+                })) => (class.ident.iter().map(Ident::to_id).collect(), true),
+                _ => continue,
+            };
+
+            ids.into_iter().for_each(|id| {
+                bindings.insert(id, Binding { idx, tdz });
+            })
+        }
+    }
+
+    /// hoist expression used by module item at `usage`, `None` if the usage is deferred,
+    /// return the expression back if no safe location exists
+    pub fn get_or_decl(&mut self, expr: Expr, usage: Option<usize>) -> Result<Ident, Expr> {
+        if let Some(ident) = self.decl_map.get(&expr) {
+            let pos = self.positions[&ident.to_id()];
+
+            return match usage {
+                Some(usage) if usage < pos => Err(expr),
+                _ => Ok(ident.clone()),
+            };
+        }
+
+        let mut collector = RefCollector::default();
+
+        expr.visit_with(&mut collector);
+
+        // imports & function declarations are hoisted, prefer but not require to place after
+        let mut required = self.start;
+        let mut preferred = self.start;
+
+        for id in collector.refs.iter() {
+            if let Some(Binding { idx, tdz }) = self.bindings.get(id) {
+                if *tdz {
+                    required = required.max(idx + 1)
+                }
+
+                preferred = preferred.max(idx + 1)
+            } else if let Some(pos) = self.positions.get(id) {
+                required = required.max(*pos);
+
+                preferred = preferred.max(*pos)
+            }
+        }
+
+        let pos = match usage {
+            Some(usage) if usage < required => return Err(expr),
+            Some(usage) => preferred.min(usage),
+            None => preferred,
+        };
+
+        let Self { name, decl_map, .. } = self;
+
+        let ident = decl_map.get_or_decl(expr, name).clone();
+
+        self.positions.insert(ident.to_id(), pos);
+
+        Ok(ident)
+    }
+
+    pub fn add_to_module(&mut self, module: &mut Module) {
+        let Self {
+            decl_map,
+            positions,
+            ..
+        } = self;
+
+        let mut groups: IndexMap<usize, Vec<_>> = IndexMap::new();
+
+        decl_map.decls().into_iter().for_each(|decl| {
+            let pos = decl
+                .name
+                .as_ident()
+                .and_then(|ident| positions.get(&ident.to_id()))
+                .copied()
+                .unwrap_or_default();
+
+            groups.entry(pos).or_default().push(decl)
+        });
+
+        groups.sort_keys();
+
+        // insert from the end, keep the index of former items
+        groups.into_iter().rev().for_each(|(pos, decls)| {
+            let decl = VarDecl {
+                kind: VarDeclKind::Const,
+                decls,
+                ..Take::dummy()
+            };
+
+            module.body.insert(pos, decl.into())
+        })
+    }
+}
@@ -0,0 +1,88 @@
+use indexmap::IndexMap;
+use swc_core::{
+    common::{util::take::Take, DUMMY_SP},
+    ecma::{
+        ast::{
+            Expr, Ident, KeyValuePatProp, Module, ObjectPat, ObjectPatProp, Pat, PropName, VarDecl,
+            VarDeclKind, VarDeclarator,
+        },
+        utils::{private_ident, quote_ident, quote_str, ExprFactory},
+    },
+};
+
+/// ## Require Helper
+/// access helpers in script programs, e.g.
+/// `const { createVNode: _createVNode } = require("vue")`
+#[derive(Debug, Default)]
+pub struct RequireHelper {
+    required_map: IndexMap<String, IndexMap<&'static str, Ident>>,
+}
+
+impl RequireHelper {
+    pub fn get_or_require(&mut self, name: &'static str, path: &str) -> &Ident {
+        self.required_map
+            .entry(path.to_string())
+            .or_default()
+            .entry(name)
+            .or_insert_with(|| private_ident!(format!("_{name}")))
+    }
+
+    /// insert helpers declaration at the top, helpers of `runtime_module` are destructured from
+    /// `global` if set, others from `require(path)`
+    pub fn add_to_module(
+        &mut self,
+        module: &mut Module,
+        global: Option<&str>,
+        runtime_module: &str,
+    ) {
+        // insert from the end, keep the order of modules
+        self.required_map
+            .drain(..)
+            .rev()
+            .for_each(|(path, ident_map)| {
+                let init = match global {
+                    Some(global) if path == runtime_module => global_expr(global),
+                    _ => quote_ident!("require").as_call(DUMMY_SP, vec![quote_str!(path).as_arg()]),
+                };
+
+                module.body.insert(0, destructure(ident_map, init).into())
+            })
+    }
+}
+
+/// `const { name: ident } = init`
+fn destructure(ident_map: IndexMap<&str, Ident>, init: Expr) -> VarDecl {
+    let props = ident_map
+        .into_iter()
+        .map(|(name, ident)| {
+            ObjectPatProp::KeyValue(KeyValuePatProp {
+                key: PropName::Ident(quote_ident!(name)),
+                value: Box::new(ident.into()),
+            })
+        })
+        .collect();
+
+    VarDecl {
+        kind: VarDeclKind::Const,
+        decls: vec![VarDeclarator {
+            name: Pat::Object(ObjectPat {
+                span: DUMMY_SP,
+                props,
+                optional: false,
+                type_ann: None,
+            }),
+            init: Some(Box::new(init)),
+            ..Take::dummy()
+        }],
+        ..Take::dummy()
+    }
+}
+
+/// dotted global path, e.g. `window.Vue`
+fn global_expr(global: &str) -> Expr {
+    let mut segments = global.split('.');
+
+    let head = Expr::from(quote_ident!(segments.next().unwrap_or_default()));
+
+    segments.fold(head, |obj, prop| obj.make_member(quote_ident!(prop)))
+}
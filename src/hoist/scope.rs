@@ -0,0 +1,62 @@
+use swc_core::ecma::ast::{Ident, PropName};
+
+use crate::{
+    constant::{RENDER, SETUP},
+    hoist::Hoist,
+    utils::is::is_prop_named,
+};
+
+/// kind of function which JSX is compiled in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FnKind {
+    Plain,
+    /// `setup()` or `defineComponent` function, run with component instance
+    Setup,
+    /// setup-returned function, `render()` method or functional component
+    Render,
+}
+
+impl FnKind {
+    /// kind of `setup` & `render` method or property
+    pub fn from_key(key: &PropName) -> Option<Self> {
+        if is_prop_named(key, SETUP) {
+            Some(Self::Setup)
+        } else if is_prop_named(key, RENDER) {
+            Some(Self::Render)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct FnScope<'a> {
+    pub kind: FnKind,
+    pub hoist: Hoist<'a>,
+    /// callback of `.map()` / `renderList()` or nested in one, rendered once per item
+    pub in_loop: bool,
+    /// IIFE, run when the enclosing code runs
+    pub immediate: bool,
+    /// loop statements depth of the enclosing function
+    pub outer_loop_depth: usize,
+    /// top-level ref bindings of `setup`, target of string refs
+    pub bindings: Vec<Ident>,
+}
+
+impl<'a> FnScope<'a> {
+    pub fn new(kind: FnKind, name: &'a str) -> Self {
+        Self {
+            kind,
+            hoist: Hoist::new(name),
+            in_loop: false,
+            immediate: false,
+            outer_loop_depth: 0,
+            bindings: Vec::new(),
+        }
+    }
+
+    /// `resolveComponent` & `resolveDirective` can be hoisted to the function
+    pub fn is_resolvable(&self) -> bool {
+        self.kind != FnKind::Plain
+    }
+}
@@ -2,105 +2,757 @@
 #![feature(let_chains)]
 #![allow(clippy::not_unsafe_ptr_arg_deref)]
 
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    mem,
+};
 
-pub use options::PluginOptions;
+use options::OptionsOverride;
+pub use options::{JSXRuntime, PluginOptions, Target, VueVersion};
 use regex::RegexSet;
 use swc_core::{
-    common::Mark,
+    common::{comments::Comments, Mark, Spanned, DUMMY_SP},
     ecma::{
-        ast::{Expr, Ident, Module, Program},
-        visit::{as_folder, noop_visit_mut_type, FoldWith, VisitMut, VisitMutWith},
-    },
-    plugin::{
-        plugin_transform,
-        proxies::{PluginCommentsProxy as Comments, TransformPluginProgramMetadata as Metadata},
+        ast::{
+            ArrowExpr, BindingIdent, BlockStmt, BlockStmtOrExpr, CallExpr, Callee, Class,
+            ClassMethod, ClassProp, Decl, DoWhileStmt, Expr, ExprOrSpread, FnDecl, ForInStmt,
+            ForOfStmt, ForStmt, Function, Id, Ident, ImportNamedSpecifier, ImportSpecifier,
+            KeyValueProp, MemberExpr, MemberProp, MethodProp, Module, ModuleDecl, ModuleExportName,
+            ModuleItem, ObjectLit, ParenExpr, Pat, Program, PropOrSpread, ReturnStmt, Script, Stmt,
+            VarDecl, VarDeclarator, WhileStmt,
+        },
+        utils::{find_pat_ids, ExprFactory},
+        visit::{as_folder, noop_visit_mut_type, Fold, FoldWith, VisitMut, VisitMutWith},
     },
+    plugin::{plugin_transform, proxies::TransformPluginProgramMetadata as Metadata},
 };
 use swc_helper_jsx_transform::shared::Transform;
-use swc_helper_module_import::ImportHelper;
+pub use transform::{transform, Diagnostic, TransformError, TransformOutput};
 
-use crate::{convert::Convert, hoist::Hoist, revise::Revise, shared::expr::ExprExtend};
+use crate::{
+    constant::{DEFINE_COMPONENT, FRAGMENT},
+    context::Context,
+    convert::{automatic::Automatic, ssr::SsrRoot, vapor::Vapor, vue2::H, Convert},
+    hoist::{
+        import::ImportHelper,
+        module::ModuleHoist,
+        require::RequireHelper,
+        scope::{FnKind, FnScope},
+        Hoist,
+    },
+    pragma::Pragma,
+    revise::Revise,
+    shared::expr::ExprExtend,
+    utils::{
+        error::emit_error,
+        is::{is_component_name, is_directive_stmt, is_fn_expr, is_ref_init},
+        jsx::JSXNormalizer,
+    },
+};
 
 mod constant;
 mod context;
 mod convert;
 mod hoist;
 mod options;
+mod pragma;
 mod revise;
 mod shared;
+mod transform;
 mod utils;
 
 #[allow(dead_code)]
-pub struct VueJSX<'a> {
-    comments: Option<Comments>,
+pub struct VueJSX<'a, C: Comments> {
+    comments: C,
     unresolved_mark: Mark,
 
     static_threshold: usize,
+    static_size_limit: usize,
+    merge_props: bool,
+    hoist: bool,
+    vue_global: Option<String>,
+    runtime_module: String,
+    helper_modules: HashMap<String, String>,
+    custom_renderer: bool,
+    target: Target,
+    vue_version: VueVersion,
+    ssr: bool,
+    jsx_runtime: JSXRuntime,
+    /// `${jsxImportSource}/jsx-runtime`
+    jsx_runtime_module: String,
     custom_tag_regex: RegexSet,
 
-    import_helper: ImportHelper<'a>,
+    is_script: bool,
+    import_helper: ImportHelper,
+    require_helper: RequireHelper,
     ident_map: HashMap<&'a str, Ident>,
-    module_hoist: Hoist<'a>,
+    /// local names of `Fragment` imported from runtime module
+    vue_fragments: HashSet<Id>,
+    module_hoist: ModuleHoist<'a>,
+    module_item_idx: usize,
     scope_hoist: Hoist<'a>,
+
+    fn_kind: Option<FnKind>,
+    fn_scopes: Vec<FnScope<'a>>,
+    /// component options need `__ssrInlineRender`
+    ssr_inline_render: bool,
+    /// loop statements depth in the current function
+    loop_depth: usize,
+    /// the next visited function is a loop callback
+    expect_loop: bool,
+    /// the next visited function is invoked immediately
+    expect_iife: bool,
+    /// converting children of a native `<p>`
+    in_paragraph: bool,
+    /// converting descendants of a `v-dynamic` element
+    in_dynamic: bool,
 }
 
-impl<'a> VueJSX<'a> {
-    pub fn new(opts: PluginOptions, comments: Option<Comments>, unresolved_mark: Mark) -> Self {
+impl<'a, C: Comments> VueJSX<'a, C> {
+    pub fn new(opts: PluginOptions, comments: C, unresolved_mark: Mark) -> Self {
         let PluginOptions {
             custom_element_patterns,
             static_threshold,
+            static_size_limit,
+            merge_props,
+            hoist,
+            vue_global,
+            runtime_module,
+            helper_modules,
+            custom_renderer,
+            target,
+            vue_version,
+            ssr,
+            jsx_runtime,
+            jsx_import_source,
         } = opts;
 
         Self {
             comments,
             unresolved_mark,
             static_threshold,
-            custom_tag_regex: RegexSet::new(custom_element_patterns).unwrap(),
+            static_size_limit,
+            merge_props,
+            hoist,
+            vue_global,
+            runtime_module,
+            helper_modules,
+            custom_renderer,
+            target,
+            vue_version,
+            ssr,
+            jsx_runtime,
+            jsx_runtime_module: format!("{jsx_import_source}/jsx-runtime"),
+            custom_tag_regex: RegexSet::new(custom_element_patterns).unwrap_or_else(|err| {
+                emit_error(
+                    DUMMY_SP,
+                    &format!("Invalid vue-jsx options, `customElementPatterns`: {err}"),
+                );
+
+                RegexSet::empty()
+            }),
+            is_script: false,
             import_helper: ImportHelper::default(),
+            require_helper: RequireHelper::default(),
             ident_map: HashMap::new(),
-            module_hoist: Hoist::new("_hoisted_"),
+            vue_fragments: HashSet::new(),
+            module_hoist: ModuleHoist::new("_hoisted_"),
+            module_item_idx: 0,
             scope_hoist: Hoist::new("_v"),
+            fn_kind: None,
+            fn_scopes: Vec::new(),
+            ssr_inline_render: false,
+            loop_depth: 0,
+            in_paragraph: false,
+            in_dynamic: false,
+            expect_loop: false,
+            expect_iife: false,
+        }
+    }
+
+    /// apply per-file pragmas from leading comments of the module,
+    /// `false` if the file is ignored
+    fn apply_pragma(&mut self, module: &Module) -> bool {
+        let positions = [
+            Some(module.span.lo),
+            module.body.first().map(|item| item.span_lo()),
+        ];
+
+        positions.into_iter().flatten().all(|pos| {
+            let mut pragma = self.comments.with_leading(pos, Pragma::parse);
+
+            if let Some(options) = pragma.options.take() {
+                self.override_options(options)
+            }
+
+            if let Some(runtime) = pragma.jsx_runtime() {
+                self.jsx_runtime = runtime
+            }
+
+            if let Some(source) = pragma.jsx_import_source {
+                self.jsx_runtime_module = format!("{source}/jsx-runtime")
+            }
+
+            !pragma.ignore
+        })
+    }
+
+    fn override_options(&mut self, options: OptionsOverride) {
+        let OptionsOverride {
+            static_threshold,
+            static_size_limit,
+            merge_props,
+            hoist,
+            custom_renderer,
+            target,
+            vue_version,
+            ssr,
+            jsx_runtime,
+        } = options;
+
+        self.static_threshold = static_threshold.unwrap_or(self.static_threshold);
+        self.static_size_limit = static_size_limit.unwrap_or(self.static_size_limit);
+        self.merge_props = merge_props.unwrap_or(self.merge_props);
+        self.hoist = hoist.unwrap_or(self.hoist);
+        self.custom_renderer = custom_renderer.unwrap_or(self.custom_renderer);
+        self.target = target.unwrap_or(self.target);
+        self.vue_version = vue_version.unwrap_or(self.vue_version);
+        self.ssr = ssr.unwrap_or(self.ssr);
+        self.jsx_runtime = jsx_runtime.unwrap_or(self.jsx_runtime);
+    }
+
+    /// `@vue-jsx-ignore` in leading comments of JSX expression
+    fn is_ignored(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::JSXElement(_) | Expr::JSXFragment(_) | Expr::Paren(_) => {
+                self.comments
+                    .with_leading(expr.span_lo(), |comments| Pragma::parse(comments).ignore)
+            },
+            _ => false,
         }
     }
 
     pub fn store(&mut self, module: &mut Module) {
-        self.import_helper.store(module)
+        self.import_helper.store(module);
+        self.module_hoist.store(module);
+
+        let path = self.helper_module(FRAGMENT, &self.runtime_module);
+
+        self.vue_fragments = imported_ids(module, FRAGMENT, &path)
     }
 
     pub fn complete(&mut self, module: &mut Module) {
-        self.import_helper.add_to_module(module);
-        self.module_hoist.add_to_module(module)
+        // hoisted positions are indexes of the original module items
+        self.module_hoist.add_to_module(module);
+
+        if self.is_script {
+            self.require_helper.add_to_module(
+                module,
+                self.vue_global.as_deref(),
+                &self.runtime_module,
+            )
+        } else {
+            self.import_helper.add_to_module(module)
+        }
+    }
+
+    /// import source of helper, `default` if not overridden
+    fn helper_module(&self, name: &str, default: &str) -> String {
+        self.helper_modules
+            .get(name)
+            .map_or(default, String::as_str)
+            .to_string()
+    }
+
+    fn import_from(&mut self, name: &'static str, path: &str) -> Ident {
+        if self.is_script {
+            self.require_helper.get_or_require(name, path).clone()
+        } else {
+            self.import_helper.get_or_import(name, path).clone()
+        }
+    }
+
+    /// kind of the next visited function
+    fn expect_fn(&mut self, kind: FnKind, expr: &Expr) {
+        if is_fn_expr(expr) {
+            self.fn_kind = Some(kind)
+        }
+    }
+
+    fn enter_fn(&mut self) {
+        let kind = self.fn_kind.take().unwrap_or(FnKind::Plain);
+
+        let is_loop_callback = mem::take(&mut self.expect_loop);
+
+        let mut scope = FnScope::new(kind, "_v");
+
+        scope.in_loop = is_loop_callback || kind == FnKind::Plain && self.is_in_loop();
+        scope.immediate = mem::take(&mut self.expect_iife);
+        scope.outer_loop_depth = mem::take(&mut self.loop_depth);
+
+        self.fn_scopes.push(scope)
+    }
+
+    /// `None` if no function is entered, `enter_fn` & `exit_fn` are paired by the visitor
+    fn exit_fn(&mut self) -> Option<FnScope<'a>> {
+        let scope = self.fn_scopes.pop()?;
+
+        self.loop_depth = scope.outer_loop_depth;
+
+        Some(scope)
+    }
+
+    /// collect top-level bindings if the current function is `setup`
+    fn collect_setup_bindings(&mut self, stmts: &[Stmt]) {
+        if let Some(scope) = self.fn_scopes.last_mut() && scope.kind == FnKind::Setup {
+            scope.bindings = stmts
+                .iter()
+                .filter_map(|stmt| {
+                    match stmt {
+                        Stmt::Decl(Decl::Var(box VarDecl { decls, .. })) => Some(decls),
+                        _ => None,
+                    }
+                })
+                .flatten()
+                .filter(|decl| is_ref_init(decl.init.as_deref()))
+                .flat_map(|decl| find_pat_ids::<_, Ident>(&decl.name))
+                .collect()
+        }
+    }
+
+    /// visit a loop statement
+    fn visit_mut_loop<T: VisitMutWith<Self>>(&mut self, node: &mut T) {
+        self.loop_depth += 1;
+
+        node.visit_mut_children_with(self);
+
+        self.loop_depth -= 1;
     }
 }
 
-impl<'a, 'b> VueJSX<'a> {
+impl<'a, 'b, C: Comments> VueJSX<'a, C> {
     pub fn compile<T, U>(&mut self, target: &'b T) -> Expr
     where
         T: Transform<'b, U>,
-        U: Revise + Convert<Expr>,
+        U: Revise + Convert<Expr> + Convert<H> + Convert<Vapor>,
+    {
+        let mut ir = target.transform();
+
+        ir.revise(self);
+
+        let expr = match self.target {
+            Target::Vue3 => ir.convert(self),
+            Target::Vue2 => {
+                let H(expr) = ir.convert(self);
+
+                expr
+            },
+            Target::Vapor => {
+                let Vapor(expr) = ir.convert(self);
+
+                expr
+            },
+        };
+
+        expr.with_hoist(&mut self.scope_hoist)
+    }
+
+    /// runtime-agnostic output, no revision for optimizations
+    pub fn compile_automatic<T, U>(&mut self, target: &'b T) -> Expr
+    where
+        T: Transform<'b, U>,
+        U: Automatic,
+    {
+        target.transform().automatic(self)
+    }
+
+    pub fn compile_ssr<T, U>(&mut self, target: &'b T) -> Vec<Stmt>
+    where
+        T: Transform<'b, U>,
+        U: Revise + SsrRoot,
     {
         let mut ir = target.transform();
 
         ir.revise(self);
 
-        ir.convert(self).with_hoist(&mut self.scope_hoist)
+        let attrs = self.get_ident("_attrs");
+        let push = self.get_ident("_push");
+
+        ir.ssr_root(attrs, self).into_stmts(&push)
+    }
+
+    /// `() => <div />` to inline ssr render `(_ctx, _push, _parent, _attrs) => { ... }`
+    fn compile_ssr_render(&mut self, arrow: &mut ArrowExpr) -> bool {
+        let ArrowExpr { params, body, .. } = arrow;
+
+        let BlockStmtOrExpr::Expr(expr) = body else {
+            return false;
+        };
+
+        if params.len() > 1
+            || !matches!(
+                unwrap_paren(expr),
+                Expr::JSXElement(_) | Expr::JSXFragment(_)
+            )
+        {
+            return false;
+        }
+
+        expr.visit_mut_with(&mut JSXNormalizer);
+
+        let stmts = match unwrap_paren(expr) {
+            Expr::JSXElement(box element) => self.compile_ssr(element),
+            Expr::JSXFragment(fragment) => self.compile_ssr(fragment),
+            _ => return false,
+        };
+
+        let ctx_param = params
+            .pop()
+            .unwrap_or_else(|| self.get_ident("_ctx").into());
+
+        *params = vec![
+            ctx_param,
+            self.get_ident("_push").into(),
+            self.get_ident("_parent").into(),
+            self.get_ident("_attrs").into(),
+        ];
+
+        *body = BlockStmtOrExpr::BlockStmt(BlockStmt {
+            span: DUMMY_SP,
+            stmts,
+        });
+
+        true
+    }
+}
+
+/// argument index of callback rendered per item, e.g. `items.map(callback)`
+fn loop_callback_idx(call: &CallExpr) -> Option<usize> {
+    let Callee::Expr(callee) = &call.callee else {
+        return None;
+    };
+
+    match &**callee {
+        Expr::Member(MemberExpr {
+            prop: MemberProp::Ident(prop),
+            ..
+        }) if matches!(&*prop.sym, "map" | "flatMap" | "forEach") => Some(0),
+        Expr::Ident(ident) if &*ident.sym == "renderList" => Some(1),
+        _ => None,
+    }
+}
+
+/// local ids of `import { name } from "path"`
+fn imported_ids(module: &Module, name: &str, path: &str) -> HashSet<Id> {
+    module
+        .body
+        .iter()
+        .filter_map(|item| {
+            match item {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(import))
+                    if &*import.src.value == path =>
+                {
+                    Some(&import.specifiers)
+                },
+                _ => None,
+            }
+        })
+        .flatten()
+        .filter_map(|specifier| {
+            let ImportSpecifier::Named(ImportNamedSpecifier {
+                local, imported, ..
+            }) = specifier else {
+                return None;
+            };
+
+            let imported_name = match imported {
+                Some(ModuleExportName::Ident(ident)) => &*ident.sym,
+                Some(ModuleExportName::Str(str)) => &*str.value,
+                None => &*local.sym,
+            };
+
+            (imported_name == name).then(|| local.to_id())
+        })
+        .collect()
+}
+
+fn unwrap_paren(expr: &Expr) -> &Expr {
+    match expr {
+        Expr::Paren(ParenExpr { expr, .. }) => unwrap_paren(expr),
+        expr => expr,
     }
 }
 
-impl<'a> VisitMut for VueJSX<'a> {
+/// `__ssrInlineRender: true`
+fn ssr_inline_render_prop() -> PropOrSpread {
+    Expr::from(true).into_prop("__ssrInlineRender")
+}
+
+impl<'a, C: Comments> VisitMut for VueJSX<'a, C> {
     noop_visit_mut_type!();
 
     fn visit_mut_module(&mut self, module: &mut Module) {
-        self.store(module);
+        // keep directive prologue in front of imports & hoisted declarations
+        let directive_count = module
+            .body
+            .iter()
+            .take_while(|item| matches!(item, ModuleItem::Stmt(stmt) if is_directive_stmt(stmt)))
+            .count();
+
+        let directives: Vec<ModuleItem> = module.body.drain(..directive_count).collect();
+
+        if self.apply_pragma(module) {
+            self.store(module);
 
-        module.visit_mut_children_with(self);
+            for (idx, item) in module.body.iter_mut().enumerate() {
+                self.module_item_idx = idx;
 
-        self.complete(module)
+                item.visit_mut_with(self)
+            }
+
+            self.complete(module);
+        }
+
+        module.body.splice(..0, directives);
+    }
+
+    fn visit_mut_script(&mut self, script: &mut Script) {
+        // process script as module of statements, helpers are required instead of imported
+        let Script {
+            span,
+            body,
+            shebang,
+        } = script;
+
+        let mut module = Module {
+            span: *span,
+            body: body.drain(..).map(ModuleItem::Stmt).collect(),
+            shebang: shebang.take(),
+        };
+
+        self.is_script = true;
+
+        self.visit_mut_module(&mut module);
+
+        *body = module
+            .body
+            .into_iter()
+            .filter_map(|item| item.stmt())
+            .collect();
+        *shebang = module.shebang;
+    }
+
+    fn visit_mut_function(&mut self, function: &mut Function) {
+        self.enter_fn();
+
+        if let Some(body) = &function.body {
+            self.collect_setup_bindings(&body.stmts)
+        }
+
+        function.visit_mut_children_with(self);
+
+        if let Some(mut scope) = self.exit_fn() && let Some(body) = &mut function.body {
+            scope.hoist.add_to_block(body)
+        }
+    }
+
+    fn visit_mut_arrow_expr(&mut self, arrow: &mut ArrowExpr) {
+        if self.ssr
+            && self.jsx_runtime == JSXRuntime::Optimized
+            && self.fn_kind == Some(FnKind::Render)
+            && matches!(
+                self.fn_scopes.last(),
+                Some(FnScope {
+                    kind: FnKind::Setup,
+                    ..
+                })
+            )
+        {
+            self.ssr_inline_render |= self.compile_ssr_render(arrow)
+        }
+
+        self.enter_fn();
+
+        if let BlockStmtOrExpr::BlockStmt(body) = &arrow.body {
+            self.collect_setup_bindings(&body.stmts)
+        }
+
+        arrow.visit_mut_children_with(self);
+
+        if let Some(mut scope) = self.exit_fn() {
+            scope.hoist.add_to_fn_body(&mut arrow.body)
+        }
+    }
+
+    fn visit_mut_for_stmt(&mut self, stmt: &mut ForStmt) {
+        self.visit_mut_loop(stmt)
+    }
+
+    fn visit_mut_for_in_stmt(&mut self, stmt: &mut ForInStmt) {
+        self.visit_mut_loop(stmt)
+    }
+
+    fn visit_mut_for_of_stmt(&mut self, stmt: &mut ForOfStmt) {
+        self.visit_mut_loop(stmt)
+    }
+
+    fn visit_mut_while_stmt(&mut self, stmt: &mut WhileStmt) {
+        self.visit_mut_loop(stmt)
+    }
+
+    fn visit_mut_do_while_stmt(&mut self, stmt: &mut DoWhileStmt) {
+        self.visit_mut_loop(stmt)
+    }
+
+    fn visit_mut_fn_decl(&mut self, decl: &mut FnDecl) {
+        if self.fn_scopes.is_empty() && is_component_name(&decl.ident.sym) {
+            self.fn_kind = Some(FnKind::Render)
+        }
+
+        decl.visit_mut_children_with(self)
+    }
+
+    fn visit_mut_var_declarator(&mut self, declarator: &mut VarDeclarator) {
+        if let VarDeclarator {
+            name: Pat::Ident(BindingIdent { id, .. }),
+            init: Some(init),
+            ..
+        } = declarator && self.fn_scopes.is_empty() && is_component_name(&id.sym)
+        {
+            self.expect_fn(FnKind::Render, init)
+        }
+
+        declarator.visit_mut_children_with(self)
+    }
+
+    fn visit_mut_method_prop(&mut self, prop: &mut MethodProp) {
+        if let Some(kind) = FnKind::from_key(&prop.key) {
+            self.fn_kind = Some(kind)
+        }
+
+        prop.visit_mut_children_with(self)
+    }
+
+    fn visit_mut_key_value_prop(&mut self, prop: &mut KeyValueProp) {
+        if let Some(kind) = FnKind::from_key(&prop.key) {
+            self.expect_fn(kind, &prop.value)
+        }
+
+        prop.visit_mut_children_with(self)
+    }
+
+    fn visit_mut_class_method(&mut self, method: &mut ClassMethod) {
+        if let Some(kind) = FnKind::from_key(&method.key) {
+            self.fn_kind = Some(kind)
+        }
+
+        method.visit_mut_children_with(self)
+    }
+
+    fn visit_mut_class_prop(&mut self, prop: &mut ClassProp) {
+        if let ClassProp {
+            key,
+            value: Some(value),
+            ..
+        } = prop && let Some(kind) = FnKind::from_key(key)
+        {
+            self.expect_fn(kind, value)
+        }
+
+        prop.visit_mut_children_with(self)
+    }
+
+    fn visit_mut_call_expr(&mut self, call: &mut CallExpr) {
+        let mut is_setup_fn = false;
+
+        if let CallExpr {
+            callee: Callee::Expr(box Expr::Ident(callee)),
+            args,
+            ..
+        } = call && &*callee.sym == DEFINE_COMPONENT && let Some(ExprOrSpread { expr, .. }) = args.first()
+        {
+            self.expect_fn(FnKind::Setup, expr);
+
+            is_setup_fn = is_fn_expr(expr)
+        }
+
+        // `(() => {})()`
+        if let Callee::Expr(callee) = &call.callee {
+            self.expect_iife = is_fn_expr(callee)
+        }
+
+        let outer = mem::take(&mut self.ssr_inline_render);
+
+        match loop_callback_idx(call) {
+            Some(idx) => {
+                call.callee.visit_mut_with(self);
+
+                for (arg_idx, arg) in call.args.iter_mut().enumerate() {
+                    self.expect_loop = arg_idx == idx && is_fn_expr(&arg.expr);
+
+                    arg.visit_mut_with(self)
+                }
+            },
+            None => call.visit_mut_children_with(self),
+        }
+
+        // `defineComponent(setup, extraOptions)`
+        if mem::replace(&mut self.ssr_inline_render, outer) && is_setup_fn && call.args.len() == 1 {
+            let options = ObjectLit {
+                span: DUMMY_SP,
+                props: vec![ssr_inline_render_prop()],
+            };
+
+            call.args.push(options.as_arg())
+        }
+    }
+
+    fn visit_mut_object_lit(&mut self, obj: &mut ObjectLit) {
+        let outer = mem::take(&mut self.ssr_inline_render);
+
+        obj.visit_mut_children_with(self);
+
+        if mem::replace(&mut self.ssr_inline_render, outer) {
+            obj.props.push(ssr_inline_render_prop())
+        }
+    }
+
+    fn visit_mut_class(&mut self, class: &mut Class) {
+        let outer = mem::take(&mut self.ssr_inline_render);
+
+        class.visit_mut_children_with(self);
+
+        self.ssr_inline_render = outer
+    }
+
+    fn visit_mut_return_stmt(&mut self, stmt: &mut ReturnStmt) {
+        if let Some(FnScope {
+            kind: FnKind::Setup,
+            ..
+        }) = self.fn_scopes.last() && let Some(arg) = &stmt.arg
+        {
+            self.expect_fn(FnKind::Render, arg)
+        }
+
+        stmt.visit_mut_children_with(self)
     }
 
     fn visit_mut_expr(&mut self, expr: &mut Expr) {
+        if self.is_ignored(expr) {
+            return;
+        }
+
+        if let Expr::JSXElement(_) | Expr::JSXFragment(_) = expr {
+            expr.visit_mut_with(&mut JSXNormalizer)
+        }
+
         match &expr {
+            Expr::JSXElement(box element) if self.jsx_runtime == JSXRuntime::Automatic => {
+                *expr = self.compile_automatic(element);
+            },
+            Expr::JSXFragment(fragment) if self.jsx_runtime == JSXRuntime::Automatic => {
+                *expr = self.compile_automatic(fragment);
+            },
             Expr::JSXElement(box element) => {
                 *expr = self.compile(element);
             },
@@ -114,6 +766,11 @@ impl<'a> VisitMut for VueJSX<'a> {
     }
 }
 
+/// Vue JSX transform, e.g. `vue_jsx(opts, Some(&comments), unresolved_mark)`
+pub fn vue_jsx<C: Comments>(opts: PluginOptions, comments: C, unresolved_mark: Mark) -> impl Fold {
+    as_folder(VueJSX::new(opts, comments, unresolved_mark))
+}
+
 #[plugin_transform]
 pub fn process_transform(program: Program, metadata: Metadata) -> Program {
     let opts = PluginOptions::from(&metadata);
@@ -124,5 +781,5 @@ pub fn process_transform(program: Program, metadata: Metadata) -> Program {
         ..
     } = metadata;
 
-    program.fold_with(&mut as_folder(VueJSX::new(opts, comments, unresolved_mark)))
+    program.fold_with(&mut vue_jsx(opts, comments, unresolved_mark))
 }
@@ -1,24 +1,217 @@
-use serde::Deserialize;
-use swc_core::plugin::metadata::TransformPluginProgramMetadata as Metadata;
+use std::collections::HashMap;
 
-#[derive(Debug, Default, Deserialize)]
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::{Map, Value};
+use swc_core::{common::DUMMY_SP, plugin::metadata::TransformPluginProgramMetadata as Metadata};
+
+use crate::utils::error::emit_error;
+
+#[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase", default)]
 pub struct PluginOptions {
     /// custom elements
     pub custom_element_patterns: Vec<String>,
-    /// static JSXElementChild hoist threshold
+    /// static JSXElementChild stringify threshold, counted by total nodes of static subtree
     /// - default: 5
     #[serde(default = "default_static_threshold")]
     pub static_threshold: usize,
+    /// max bytes of html content stringified into a single static vnode
+    /// - default: 16384
+    #[serde(default = "default_static_size_limit")]
+    pub static_size_limit: usize,
+    /// merge duplicate `class`, `style` and `on*` props like `mergeProps`,
+    /// otherwise the later prop overrides
+    /// - default: true
+    #[serde(default = "default_merge_props")]
+    pub merge_props: bool,
+    /// hoist static vnodes & template factories to module scope
+    /// - default: true
+    #[serde(default = "default_hoist")]
+    pub hoist: bool,
+    /// global variable to access helpers in script programs, e.g. `Vue` for IIFE builds,
+    /// otherwise helpers are accessed through `require("vue")`
+    pub vue_global: Option<String>,
+    /// import source of runtime helpers, e.g. `@vue/runtime-dom`
+    /// - default: "vue"
+    #[serde(default = "default_runtime_module")]
+    pub runtime_module: String,
+    /// import source per helper, override `runtime_module`
+    pub helper_modules: HashMap<String, String>,
+    /// target custom renderer, lowercase tags are renderer primitives,
+    /// DOM specific optimizations like `createStaticVNode` are disabled
+    pub custom_renderer: bool,
+    /// compile target
+    /// - default: "vue3"
+    pub target: Target,
+    /// targeted Vue 3 runtime version, selects helpers, patch flag values are the same across minors
+    /// - default: "3.5"
+    pub vue_version: VueVersion,
+    /// compile setup-returned render functions to inline ssr render,
+    /// pushing html strings by `vue/server-renderer` helpers,
+    /// only arrow functions returned from `setup` with a JSX body are compiled,
+    /// other JSX still creates vnodes
+    pub ssr: bool,
+    /// "automatic" emits runtime-agnostic `jsx()` / `jsxs()` calls instead of optimized vnodes,
+    /// overridden per file by `@jsxRuntime` & `@jsxImportSource` pragmas
+    /// - default: "optimized"
+    pub jsx_runtime: JSXRuntime,
+    /// automatic runtime helpers are imported from `${jsxImportSource}/jsx-runtime`
+    /// - default: "vue"
+    #[serde(default = "default_runtime_module")]
+    pub jsx_import_source: String,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum Target {
+    #[default]
+    #[serde(rename = "vue3")]
+    Vue3,
+    /// `h()` with Vue 2 data object, no patch flags & static vnodes
+    #[serde(rename = "vue2.7")]
+    Vue2,
+    /// experimental, `template()` factories with `renderEffect` bindings, no vnodes
+    #[serde(rename = "vapor")]
+    Vapor,
+}
+
+/// per-file overrides of `PluginOptions` by `@vue-jsx` pragma, unknown keys are rejected
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct OptionsOverride {
+    pub static_threshold: Option<usize>,
+    pub static_size_limit: Option<usize>,
+    pub merge_props: Option<bool>,
+    pub hoist: Option<bool>,
+    pub custom_renderer: Option<bool>,
+    pub target: Option<Target>,
+    pub vue_version: Option<VueVersion>,
+    pub ssr: Option<bool>,
+    pub jsx_runtime: Option<JSXRuntime>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum JSXRuntime {
+    #[default]
+    Optimized,
+    Automatic,
+}
+
+impl Default for PluginOptions {
+    fn default() -> Self {
+        Self {
+            custom_element_patterns: Vec::new(),
+            static_threshold: default_static_threshold(),
+            static_size_limit: default_static_size_limit(),
+            merge_props: default_merge_props(),
+            hoist: default_hoist(),
+            vue_global: None,
+            runtime_module: default_runtime_module(),
+            helper_modules: HashMap::new(),
+            custom_renderer: false,
+            target: Target::default(),
+            vue_version: VueVersion::default(),
+            ssr: false,
+            jsx_runtime: JSXRuntime::default(),
+            jsx_import_source: default_runtime_module(),
+        }
+    }
 }
 
 const fn default_static_threshold() -> usize {
     5
 }
 
+const fn default_static_size_limit() -> usize {
+    16 * 1024
+}
+
+const fn default_merge_props() -> bool {
+    true
+}
+
+const fn default_hoist() -> bool {
+    true
+}
+
+/// `major.minor` version of Vue runtime, e.g. "3.4"
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(try_from = "String")]
+pub struct VueVersion {
+    pub major: u32,
+    pub minor: u32,
+}
+
+impl VueVersion {
+    pub const fn new(major: u32, minor: u32) -> Self {
+        Self { major, minor }
+    }
+
+    pub fn is_at_least(&self, major: u32, minor: u32) -> bool {
+        *self >= Self::new(major, minor)
+    }
+}
+
+impl Default for VueVersion {
+    fn default() -> Self {
+        Self::new(3, 5)
+    }
+}
+
+impl TryFrom<String> for VueVersion {
+    type Error = String;
+
+    fn try_from(version: String) -> Result<Self, Self::Error> {
+        let mut segments = version.split('.').map(str::parse::<u32>);
+
+        match (segments.next(), segments.next()) {
+            (Some(Ok(major @ 3)), Some(Ok(minor))) => Ok(Self::new(major, minor)),
+            _ => Err(format!("Invalid Vue 3 version: {version}")),
+        }
+    }
+}
+
+fn default_runtime_module() -> String {
+    "vue".to_string()
+}
+
+/// deserialize options object, the error names the offending option key
+pub fn parse_json<T: DeserializeOwned>(json: &str) -> Result<T, String> {
+    let value: Value = serde_json::from_str(json).map_err(|err| err.to_string())?;
+
+    serde_json::from_value(value.clone()).map_err(|err| {
+        // retry keys one by one to find the invalid one
+        let key = value.as_object().and_then(|object| {
+            object.iter().find_map(|(key, value)| {
+                let single = Map::from_iter([(key.clone(), value.clone())]);
+
+                serde_json::from_value::<T>(Value::Object(single))
+                    .is_err()
+                    .then_some(key)
+            })
+        });
+
+        match key {
+            Some(key) => format!("`{key}`: {err}"),
+            None => err.to_string(),
+        }
+    })
+}
+
+impl PluginOptions {
+    pub fn parse(json: &str) -> Result<Self, String> {
+        parse_json(json)
+    }
+}
+
+/// invalid options are reported as an error, defaults are used instead
 impl From<&str> for PluginOptions {
     fn from(s: &str) -> Self {
-        serde_json::from_str(s).expect("Error: Invalid Options")
+        Self::parse(s).unwrap_or_else(|err| {
+            emit_error(DUMMY_SP, &format!("Invalid vue-jsx options, {err}"));
+
+            Self::default()
+        })
     }
 }
 
@@ -0,0 +1,76 @@
+use swc_core::common::comments::Comment;
+
+use crate::{
+    options::{parse_json, JSXRuntime, OptionsOverride},
+    regex,
+    utils::error::emit_error,
+};
+
+/// ## Pragma
+/// per-file options from comments, e.g. `/** @jsxImportSource vue */`
+#[derive(Debug, Default)]
+pub struct Pragma {
+    /// `@jsxRuntime automatic | optimized`
+    pub jsx_runtime: Option<JSXRuntime>,
+    /// `@jsxImportSource <source>`, implies automatic runtime
+    pub jsx_import_source: Option<String>,
+    /// `@vue-jsx-ignore`, leave the file or JSX subtree untransformed
+    pub ignore: bool,
+    /// `@vue-jsx staticThreshold: 10, hoist: false`
+    pub options: Option<OptionsOverride>,
+}
+
+impl Pragma {
+    pub fn parse(comments: &[Comment]) -> Self {
+        let mut pragma = Self::default();
+
+        comments.iter().for_each(|comment| {
+            let text = &*comment.text;
+
+            if let Some(captures) = regex!(r"@jsxRuntime\s+(\S+)").captures(text) {
+                match &captures[1] {
+                    "automatic" => pragma.jsx_runtime = Some(JSXRuntime::Automatic),
+                    "optimized" => pragma.jsx_runtime = Some(JSXRuntime::Optimized),
+                    _ => {},
+                }
+            }
+
+            if let Some(captures) = regex!(r"@jsxImportSource\s+(\S+)").captures(text) {
+                pragma.jsx_import_source = Some(captures[1].to_string())
+            }
+
+            if regex!(r"@vue-jsx-ignore\b").is_match(text) {
+                pragma.ignore = true
+            }
+
+            if let Some(captures) = regex!(r"@vue-jsx\s+(.+)").captures(text) {
+                match parse_options(&captures[1]) {
+                    Ok(options) => pragma.options = Some(options),
+                    Err(err) => {
+                        emit_error(comment.span, &format!("Invalid @vue-jsx pragma, {err}"))
+                    },
+                }
+            }
+        });
+
+        pragma
+    }
+
+    /// `@jsxRuntime` takes precedence over the implied runtime of `@jsxImportSource`
+    pub fn jsx_runtime(&self) -> Option<JSXRuntime> {
+        self.jsx_runtime.or_else(|| {
+            self.jsx_import_source
+                .as_ref()
+                .map(|_| JSXRuntime::Automatic)
+        })
+    }
+}
+
+/// options in object literal syntax without braces, keys may be unquoted
+fn parse_options(text: &str) -> Result<OptionsOverride, String> {
+    let object = format!("{{{}}}", text.trim());
+
+    let json = regex!(r"([{,]\s*)([A-Za-z_$][\w$]*)\s*:").replace_all(&object, r#"$1"$2":"#);
+
+    parse_json(&json)
+}
@@ -1,11 +1,19 @@
 use swc_helper_jsx_transform::{
-    attr::{key::Key, Attr},
+    attr::{key::Key, value::Value, Attr},
     element::{tag::Tag, Element},
     fragment::Fragment,
+    utils::is::is_native_tag,
     vnode::VNode,
 };
 
-use crate::{context::Context, utils::is::is_directive};
+use crate::{
+    constant::{CLASS, KEY, REF, STYLE, V_DYNAMIC, V_STATIC},
+    context::Context,
+    utils::{
+        is::is_directive,
+        normalize::{normalize_class, normalize_style},
+    },
+};
 
 pub trait Revise {
     fn revise<C: Context>(&mut self, ctx: &mut C);
@@ -22,21 +30,41 @@ impl<'a> Revise for Tag<'a> {
         if let Self::Extra(ident) = self {
             let name = &*ident.sym;
 
-            if ctx.is_custom_element(name) {
+            // lowercase unresolved tags are primitives of custom renderer
+            let is_primitive = ctx.is_custom_renderer()
+                && ctx.is_unresolved(ident)
+                && name.starts_with(|c: char| c.is_ascii_lowercase());
+
+            if is_primitive || ctx.is_custom_element(name) {
                 *self = Self::Native(name)
             }
         }
     }
 }
 
-fn has_directive(attrs: &[Attr]) -> bool {
-    attrs.iter().any(|Attr { key, .. }| {
-        if let Key::Attr(name) = key && is_directive(name) {
-            true
-        } else {
-            false
-        }
-    })
+/// hoisted vnodes are shared, refs / keys / events / directives need a vnode per render
+fn is_static_attr(attr: &Attr) -> bool {
+    let Attr { key, value } = attr;
+
+    match (key, value) {
+        (Key::Attr(V_STATIC | V_DYNAMIC), _) => true,
+        (Key::Attr(REF | KEY) | Key::Event(_) | Key::Spread, _) => false,
+        (Key::Attr(name), _) if is_directive(name) => false,
+        (Key::NSAttr { ns, .. }, _) if is_directive(ns) => false,
+        (Key::Attr(CLASS), Value::Const(expr)) => normalize_class(expr).is_some(),
+        (Key::Attr(STYLE), Value::Const(expr)) => normalize_style(expr).is_some(),
+        _ => value.is_static(),
+    }
+}
+
+/// elements of the renderer after tag revision, custom elements & components are not hoisted
+fn is_hoistable_tag<C: Context>(ctx: &C, tag: &Tag) -> bool {
+    match tag {
+        Tag::Native(name) => {
+            is_native_tag(name) || ctx.is_custom_renderer() && !ctx.is_custom_element(name)
+        },
+        _ => false,
+    }
 }
 
 fn has_dyn_children(children: &[VNode]) -> bool {
@@ -57,12 +85,87 @@ impl<'a> Revise for Element<'a> {
 
         children.revise(ctx);
 
-        if *is_static && (has_dyn_children(children) || has_directive(attrs)) {
-            *is_static = false
+        *is_static = ctx.is_hoist()
+            && is_hoistable_tag(ctx, tag)
+            && attrs.iter().all(is_static_attr)
+            && !has_dyn_children(children);
+
+        if has_hint(self, V_DYNAMIC) {
+            force_dynamic(self)
+        } else if has_hint(self, V_STATIC) && ctx.is_hoist() {
+            vouch_static(self, ctx);
         }
     }
 }
 
+pub fn has_hint(element: &Element, hint: &str) -> bool {
+    element
+        .attrs
+        .iter()
+        .any(|Attr { key, .. }| matches!(key, Key::Attr(name) if *name == hint))
+}
+
+/// `v-dynamic`, keep the subtree out of hoisting & stringification,
+/// text vnodes have no static flag and are kept in place by `Context::is_in_dynamic`
+fn force_dynamic(element: &mut Element) {
+    element.is_static = false;
+
+    element.children.iter_mut().for_each(force_dynamic_vnode)
+}
+
+fn force_dynamic_vnode(vnode: &mut VNode) {
+    match vnode {
+        VNode::Element(element) => force_dynamic(element),
+        VNode::Fragment(fragment) => {
+            fragment.is_static = false;
+
+            fragment.children.iter_mut().for_each(force_dynamic_vnode)
+        },
+        _ => {},
+    }
+}
+
+/// `v-static`, expressions of the subtree are vouched as constant
+fn vouch_static<C: Context>(element: &mut Element, ctx: &C) -> bool {
+    if has_hint(element, V_DYNAMIC) {
+        force_dynamic(element);
+
+        return false;
+    }
+
+    let is_children_static = vouch_static_children(&mut element.children, ctx);
+
+    element.is_static = is_hoistable_tag(ctx, &element.tag)
+        && element.attrs.iter().all(is_vouched_attr)
+        && is_children_static;
+
+    element.is_static
+}
+
+fn vouch_static_children<C: Context>(children: &mut [VNode], ctx: &C) -> bool {
+    children.iter_mut().fold(true, |is_static, child| {
+        let is_child_static = match child {
+            VNode::Element(element) => vouch_static(element, ctx),
+            VNode::Fragment(fragment) => vouch_static_children(&mut fragment.children, ctx),
+            VNode::Text(_) | VNode::Expr(_) => true,
+            VNode::Spread(_) => false,
+        };
+
+        is_static && is_child_static
+    })
+}
+
+/// expressions are vouched, other rules of `is_static_attr` still apply
+fn is_vouched_attr(attr: &Attr) -> bool {
+    match attr.key {
+        Key::Attr(V_STATIC | V_DYNAMIC) => true,
+        Key::Attr(REF | KEY) | Key::Event(_) => false,
+        Key::Attr(name) => !is_directive(name),
+        Key::NSAttr { ns, .. } => !is_directive(ns),
+        Key::Spread => true,
+    }
+}
+
 impl<'a> Revise for Fragment<'a> {
     fn revise<C: Context>(&mut self, ctx: &mut C) {
         self.children.revise(ctx)
@@ -1,8 +1,10 @@
 use swc_core::{
     common::{util::take::Take, DUMMY_SP},
     ecma::{
-        ast::{ArrowExpr, BlockStmt, BlockStmtOrExpr, Expr},
-        utils::ExprFactory,
+        ast::{
+            ArrowExpr, BlockStmt, BlockStmtOrExpr, Expr, Ident, KeyValueProp, Prop, PropOrSpread,
+        },
+        utils::{quote_ident, quote_str, ExprFactory},
     },
 };
 
@@ -14,11 +16,13 @@ pub trait ExprExtend {
     fn hoist_to_scope<C: Context>(self, ctx: &mut C) -> Expr;
 
     fn with_hoist(self, hoist: &mut Hoist) -> Expr;
+
+    fn into_prop(self, name: &str) -> PropOrSpread;
 }
 
 impl ExprExtend for Expr {
     fn hoist_to_module<C: Context>(self, ctx: &mut C) -> Expr {
-        ctx.hoist_to_module(self).into()
+        ctx.hoist_to_module(self)
     }
 
     fn hoist_to_scope<C: Context>(self, ctx: &mut C) -> Expr {
@@ -41,4 +45,18 @@ impl ExprExtend for Expr {
             None => self,
         }
     }
+
+    fn into_prop(self, name: &str) -> PropOrSpread {
+        let key = if Ident::verify_symbol(name).is_ok() {
+            quote_ident!(name).into()
+        } else {
+            quote_str!(name).into()
+        };
+
+        Prop::KeyValue(KeyValueProp {
+            key,
+            value: Box::new(self),
+        })
+        .into()
+    }
 }
@@ -0,0 +1,227 @@
+use std::{
+    error::Error,
+    fmt, mem,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use swc_core::{
+    common::{
+        chain,
+        comments::SingleThreadedComments,
+        errors::{DiagnosticBuilder, Emitter, Handler, Level, HANDLER},
+        pass::Optional,
+        sync::Lrc,
+        FileName, Globals, Mark, SourceMap, GLOBALS,
+    },
+    ecma::{
+        ast::{EsVersion, Program},
+        codegen::{self, text_writer::JsWriter},
+        parser::{EsConfig, Parser, StringInput, Syntax, TsConfig},
+        transforms::{
+            base::{fixer::fixer, hygiene::hygiene, resolver},
+            typescript::strip,
+        },
+        visit::FoldWith,
+    },
+};
+
+use crate::{options::PluginOptions, vue_jsx};
+
+#[derive(Debug)]
+pub struct TransformOutput {
+    pub code: String,
+    /// source map v3 json
+    pub map: String,
+    /// warnings & recoverable errors
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// compiler message, `location` is 1-based `(line, column)` of the primary span
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub level: Level,
+    pub message: String,
+    pub location: Option<(usize, usize)>,
+}
+
+impl Diagnostic {
+    pub fn is_error(&self) -> bool {
+        matches!(
+            self.level,
+            Level::Bug | Level::Fatal | Level::PhaseFatal | Level::Error
+        )
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some((line, column)) = self.location {
+            write!(f, "{line}:{column}: ")?;
+        }
+
+        write!(f, "{}: {}", self.level, self.message)
+    }
+}
+
+/// source can't be parsed or emitted
+#[derive(Debug)]
+pub struct TransformError {
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl fmt::Display for TransformError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let messages: Vec<String> = self.diagnostics.iter().map(Diagnostic::to_string).collect();
+
+        write!(f, "{}", messages.join("\n"))
+    }
+}
+
+impl Error for TransformError {}
+
+pub type Result<T, E = TransformError> = std::result::Result<T, E>;
+
+/// collect diagnostics instead of printing to stderr
+#[derive(Clone)]
+struct Collector {
+    cm: Lrc<SourceMap>,
+    diagnostics: Arc<Mutex<Vec<Diagnostic>>>,
+}
+
+impl Collector {
+    fn new(cm: Lrc<SourceMap>) -> Self {
+        Self {
+            cm,
+            diagnostics: Default::default(),
+        }
+    }
+
+    fn take(&self) -> Vec<Diagnostic> {
+        mem::take(&mut self.diagnostics.lock().unwrap())
+    }
+}
+
+impl Emitter for Collector {
+    fn emit(&mut self, db: &DiagnosticBuilder<'_>) {
+        let location = db
+            .span
+            .primary_span()
+            .filter(|span| !span.is_dummy())
+            .map(|span| {
+                let loc = self.cm.lookup_char_pos(span.lo);
+
+                (loc.line, loc.col.0 + 1)
+            });
+
+        self.diagnostics.lock().unwrap().push(Diagnostic {
+            level: db.level,
+            message: db.message(),
+            location,
+        })
+    }
+}
+
+/// `.js` / `.jsx` are parsed as JSX, others as TSX
+fn syntax(filename: &str) -> Syntax {
+    match Path::new(filename).extension().and_then(|ext| ext.to_str()) {
+        Some("js" | "jsx" | "mjs" | "cjs") => {
+            Syntax::Es(EsConfig {
+                jsx: true,
+                ..Default::default()
+            })
+        },
+        _ => {
+            Syntax::Typescript(TsConfig {
+                tsx: true,
+                ..Default::default()
+            })
+        },
+    }
+}
+
+/// compile Vue JSX source to code & source map,
+/// source is parsed as ES module, runs `resolver`, `VueJSX`, TypeScript `strip`, `hygiene` and `fixer`
+pub fn transform(source: &str, filename: &str, opts: PluginOptions) -> Result<TransformOutput> {
+    let cm: Lrc<SourceMap> = Default::default();
+    let comments = SingleThreadedComments::default();
+
+    let collector = Collector::new(cm.clone());
+    let handler = Handler::with_emitter(true, false, Box::new(collector.clone()));
+
+    let result = GLOBALS.set(&Globals::new(), || {
+        HANDLER.set(&handler, || {
+            let fm = cm.new_source_file(FileName::Real(filename.into()), source.into());
+
+            let syntax = syntax(filename);
+
+            let mut parser = Parser::new(syntax, StringInput::from(&*fm), Some(&comments));
+
+            // imports are added to the output, parse as module even without import / export
+            let module = parser.parse_module();
+
+            for err in parser.take_errors() {
+                err.into_diagnostic(&handler).emit()
+            }
+
+            let program = match module {
+                Ok(module) => Program::Module(module),
+                Err(err) => {
+                    err.into_diagnostic(&handler).emit();
+                    return None;
+                },
+            };
+
+            let unresolved_mark = Mark::new();
+            let top_level_mark = Mark::new();
+
+            let program: Program = program.fold_with(&mut chain!(
+                resolver(unresolved_mark, top_level_mark, syntax.typescript()),
+                vue_jsx(opts, Some(&comments), unresolved_mark),
+                Optional::new(strip(top_level_mark), syntax.typescript()),
+                hygiene(),
+                fixer(Some(&comments))
+            ));
+
+            let mut code = vec![];
+            let mut mappings = vec![];
+
+            let mut emitter = codegen::Emitter {
+                cfg: codegen::Config {
+                    target: EsVersion::latest(),
+                    ..Default::default()
+                },
+                cm: cm.clone(),
+                comments: Some(&comments),
+                wr: JsWriter::new(cm.clone(), "\n", &mut code, Some(&mut mappings)),
+            };
+
+            if let Err(err) = emitter.emit_program(&program) {
+                handler.err(&err.to_string());
+                return None;
+            }
+
+            let mut map = vec![];
+
+            if let Err(err) = cm.build_source_map(&mappings).to_writer(&mut map) {
+                handler.err(&err.to_string());
+                return None;
+            }
+
+            Some((code, map))
+        })
+    });
+
+    let diagnostics = collector.take();
+
+    match result {
+        Some((code, map)) => {
+            Ok(TransformOutput {
+                code: String::from_utf8(code).unwrap_or_default(),
+                map: String::from_utf8(map).unwrap_or_default(),
+                diagnostics,
+            })
+        },
+        None => Err(TransformError { diagnostics }),
+    }
+}
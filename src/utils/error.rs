@@ -0,0 +1,9 @@
+use swc_core::common::{errors::HANDLER, Span};
+
+/// report a compiler error at `span`, the build fails after the transform,
+/// dropped when no handler is set, e.g. `vue_jsx` used as a bare `Fold`
+pub fn emit_error(span: Span, msg: &str) {
+    if HANDLER.is_set() {
+        HANDLER.with(|handler| handler.struct_span_err(span, msg).emit())
+    }
+}
@@ -0,0 +1,26 @@
+/// escape html text & attribute value, same as `escapeHtml` in `@vue/shared`
+pub fn escape_html(text: &str) -> String {
+    let mut buf = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '"' => buf.push_str("&quot;"),
+            '&' => buf.push_str("&amp;"),
+            '\'' => buf.push_str("&#39;"),
+            '<' => buf.push_str("&lt;"),
+            '>' => buf.push_str("&gt;"),
+            c => buf.push(c),
+        }
+    }
+
+    buf
+}
+
+/// JS `Number.prototype.toString` for finite literal
+pub fn stringify_number(num: f64) -> String {
+    if num.fract() == 0.0 && num.abs() < 1e21 {
+        format!("{num:.0}")
+    } else {
+        num.to_string()
+    }
+}
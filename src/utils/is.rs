@@ -1,10 +1,63 @@
+use swc_core::ecma::ast::{CallExpr, Callee, Expr, ExprStmt, Lit, ParenExpr, PropName, Stmt, Str};
+
 use crate::{constant::V_MODEL_NATIVE_ELEMENT, regex};
 
 pub fn is_directive(text: &str) -> bool {
     regex!("^v-").is_match(text)
 }
 
+pub fn is_attr_name(text: &str) -> bool {
+    regex!(r#"^[^\s"'>/=]+$"#).is_match(text)
+}
+
 #[allow(dead_code)]
 pub fn is_native_v_model_tag(tag: &str) -> bool {
     V_MODEL_NATIVE_ELEMENT.contains(&tag)
 }
+
+pub fn is_fn_expr(expr: &Expr) -> bool {
+    match expr {
+        Expr::Fn(_) | Expr::Arrow(_) => true,
+        Expr::Paren(ParenExpr { expr, .. }) => is_fn_expr(expr),
+        _ => false,
+    }
+}
+
+pub fn is_prop_named(key: &PropName, name: &str) -> bool {
+    match key {
+        PropName::Ident(ident) => &*ident.sym == name,
+        PropName::Str(Str { value, .. }) => &**value == name,
+        _ => false,
+    }
+}
+
+/// binding may hold a template ref, `ref()` / `shallowRef()` / `useTemplateRef()` or uninitialized
+pub fn is_ref_init(init: Option<&Expr>) -> bool {
+    let Some(init) = init else {
+        return true;
+    };
+
+    matches!(
+        init,
+        Expr::Call(CallExpr {
+            callee: Callee::Expr(box Expr::Ident(callee)),
+            ..
+        }) if matches!(&*callee.sym, "ref" | "shallowRef" | "useTemplateRef")
+    )
+}
+
+/// PascalCase binding, e.g. functional component
+pub fn is_component_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_uppercase())
+}
+
+/// directive prologue, e.g. `"use strict"`
+pub fn is_directive_stmt(stmt: &Stmt) -> bool {
+    matches!(
+        stmt,
+        Stmt::Expr(ExprStmt {
+            expr: box Expr::Lit(Lit::Str(_)),
+            ..
+        })
+    )
+}
@@ -0,0 +1,120 @@
+use std::mem;
+
+use swc_core::{
+    common::Spanned,
+    ecma::{
+        ast::{
+            Expr, Ident, JSXAttr, JSXAttrOrSpread, JSXAttrValue, JSXClosingElement, JSXElement,
+            JSXElementChild, JSXElementName, JSXExpr, JSXExprContainer, JSXFragment,
+            JSXNamespacedName, JSXText,
+        },
+        visit::{noop_visit_mut_type, VisitMut, VisitMutWith},
+    },
+};
+use swc_helper_jsx_transform::text::Text;
+
+use crate::utils::error::emit_error;
+
+/// ## JSX Normalizer
+/// prepare JSX for the IR transform, which only accepts a subset of the syntax:
+/// `{/* comment */}` children are dropped, nested `<>...</>` children are flattened,
+/// adjacent texts are merged, namespaced tags are reported.
+/// JSX attribute values are transformed with the element, other expressions are separate roots
+pub struct JSXNormalizer;
+
+impl VisitMut for JSXNormalizer {
+    noop_visit_mut_type!();
+
+    fn visit_mut_jsx_element(&mut self, element: &mut JSXElement) {
+        if let JSXElementName::JSXNamespacedName(name) = &element.opening.name {
+            emit_error(name.span(), "Namespaced JSX tags are not supported");
+
+            let name = JSXElementName::Ident(namespaced_ident(name));
+
+            if let Some(JSXClosingElement { name: closing, .. }) = &mut element.closing {
+                *closing = name.clone()
+            }
+
+            element.opening.name = name
+        }
+
+        element
+            .opening
+            .attrs
+            .iter_mut()
+            .for_each(|attr| self.normalize_attr(attr));
+
+        self.normalize_children(&mut element.children)
+    }
+
+    fn visit_mut_jsx_fragment(&mut self, fragment: &mut JSXFragment) {
+        self.normalize_children(&mut fragment.children)
+    }
+}
+
+impl JSXNormalizer {
+    /// `attr=<div />` & `attr={<div />}`
+    fn normalize_attr(&mut self, attr: &mut JSXAttrOrSpread) {
+        let JSXAttrOrSpread::JSXAttr(JSXAttr { value: Some(value), .. }) = attr else {
+            return;
+        };
+
+        match value {
+            JSXAttrValue::JSXElement(element) => element.visit_mut_with(self),
+            JSXAttrValue::JSXFragment(fragment) => fragment.visit_mut_with(self),
+            JSXAttrValue::JSXExprContainer(JSXExprContainer {
+                expr: JSXExpr::Expr(expr),
+                ..
+            }) if matches!(&**expr, Expr::JSXElement(_) | Expr::JSXFragment(_)) => {
+                expr.visit_mut_with(self)
+            },
+            _ => {},
+        }
+    }
+
+    fn normalize_children(&mut self, children: &mut Vec<JSXElementChild>) {
+        let flattened = mem::take(children).into_iter().flat_map(|child| {
+            match child {
+                JSXElementChild::JSXExprContainer(JSXExprContainer {
+                    expr: JSXExpr::JSXEmptyExpr(_),
+                    ..
+                }) => vec![],
+                JSXElementChild::JSXFragment(mut fragment) => {
+                    self.normalize_children(&mut fragment.children);
+
+                    fragment.children
+                },
+                JSXElementChild::JSXElement(mut element) => {
+                    element.visit_mut_with(self);
+
+                    vec![JSXElementChild::JSXElement(element)]
+                },
+                child => vec![child],
+            }
+        });
+
+        flattened.for_each(|child| {
+            match (children.last_mut(), child) {
+                (Some(JSXElementChild::JSXText(prev)), JSXElementChild::JSXText(text)) => {
+                    merge_text(prev, &text)
+                },
+                (_, child) => children.push(child),
+            }
+        })
+    }
+}
+
+/// html parser merges adjacent texts into one text node,
+/// each text is cleaned on its own like separate JSX texts
+fn merge_text(prev: &mut JSXText, text: &JSXText) {
+    let value = Text::clean(&prev.value) + &Text::clean(&text.value);
+
+    prev.span = prev.span.with_hi(text.span.hi);
+    prev.raw = format!("{}{}", prev.raw, text.raw).into();
+    prev.value = value.into();
+}
+
+/// `svg:rect` as a single name, only reached with an error reported
+fn namespaced_ident(JSXNamespacedName { ns, name }: &JSXNamespacedName) -> Ident {
+    Ident::new(format!("{}:{}", ns.sym, name.sym).into(), name.span)
+}
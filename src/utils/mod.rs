@@ -1,3 +1,7 @@
 pub mod ast_marco;
+pub mod error;
+pub mod html;
 pub mod is;
+pub mod jsx;
+pub mod normalize;
 pub mod regex_macro;
@@ -0,0 +1,259 @@
+use indexmap::IndexMap;
+use swc_core::{
+    common::DUMMY_SP,
+    ecma::{
+        ast::{
+            ArrayLit, Bool, Expr, ExprOrSpread, KeyValueProp, Lit, Number, ObjectLit, ParenExpr,
+            Prop, PropName, PropOrSpread, Str,
+        },
+        utils::quote_str,
+    },
+};
+use swc_helper_jsx_transform::utils::is::is_undefined_ident;
+
+use crate::{regex, shared::expr::ExprExtend, utils::html::stringify_number};
+
+/// normalized style, value is string or number literal
+pub type Style = IndexMap<String, Lit>;
+
+/// `true` if [Expr] is array or object literal, which runtime `normalizeClass` / `normalizeStyle`
+/// is required
+pub fn is_normalizable(expr: &Expr) -> bool {
+    match expr {
+        Expr::Array(_) | Expr::Object(_) => true,
+        Expr::Paren(ParenExpr { expr, .. }) => is_normalizable(expr),
+        _ => false,
+    }
+}
+
+fn truthy(expr: &Expr) -> Option<bool> {
+    match expr {
+        Expr::Lit(lit) => {
+            match lit {
+                Lit::Bool(Bool { value, .. }) => Some(*value),
+                Lit::Num(Number { value, .. }) => Some(*value != 0.0 && !value.is_nan()),
+                Lit::Str(Str { value, .. }) => Some(!value.is_empty()),
+                Lit::Null(_) => Some(false),
+                _ => None,
+            }
+        },
+        Expr::Ident(ident) if is_undefined_ident(ident) => Some(false),
+        Expr::Array(_) | Expr::Object(_) => Some(true),
+        Expr::Paren(ParenExpr { expr, .. }) => truthy(expr),
+        _ => None,
+    }
+}
+
+fn prop_name(name: &PropName) -> Option<String> {
+    match name {
+        PropName::Ident(ident) => Some(ident.sym.to_string()),
+        PropName::Str(Str { value, .. }) => Some(value.to_string()),
+        PropName::Num(Number { value, .. }) => Some(stringify_number(*value)),
+        PropName::Computed(_) | PropName::BigInt(_) => None,
+    }
+}
+
+fn key_values(props: &[PropOrSpread]) -> Option<Vec<(String, &Expr)>> {
+    props
+        .iter()
+        .map(|prop| {
+            match prop {
+                PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp { key, value })) => {
+                    prop_name(key).map(|name| (name, &**value))
+                },
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+fn class_item(expr: &Expr, buf: &mut Vec<String>) -> Option<()> {
+    match expr {
+        Expr::Lit(Lit::Str(Str { value, .. })) => buf.push(value.to_string()),
+        Expr::Array(ArrayLit { elems, .. }) => {
+            for elem in elems.iter().flatten() {
+                match elem {
+                    ExprOrSpread {
+                        spread: Some(_), ..
+                    } => return None,
+                    ExprOrSpread { expr, .. } => class_item(expr, buf)?,
+                }
+            }
+        },
+        Expr::Object(ObjectLit { props, .. }) => {
+            for (name, value) in key_values(props)? {
+                if truthy(value)? {
+                    buf.push(name)
+                }
+            }
+        },
+        Expr::Paren(ParenExpr { expr, .. }) => class_item(expr, buf)?,
+        expr => {
+            // other constants are ignored by `normalizeClass`
+            truthy(expr)?;
+        },
+    }
+
+    Some(())
+}
+
+/// ## [normalizeClass](https://github.com/vuejs/core/blob/main/packages/shared/src/normalizeProp.ts)
+/// `None` if the class is not a constant string, array or object literal
+pub fn normalize_class(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(Lit::Str(Str { value, .. })) => Some(value.to_string()),
+        Expr::Array(_) | Expr::Object(_) | Expr::Paren(_) => {
+            let mut buf = Vec::new();
+
+            class_item(expr, &mut buf)?;
+
+            let class = buf
+                .iter()
+                .map(|item| item.trim())
+                .filter(|item| !item.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            Some(class)
+        },
+        _ => None,
+    }
+}
+
+/// ## [parseStringStyle](https://github.com/vuejs/core/blob/main/packages/shared/src/normalizeProp.ts)
+fn parse_string_style(text: &str, style: &mut Style) {
+    let text = regex!(r"/\*[\s\S]*?\*/").replace_all(text, "");
+
+    let mut depth = 0usize;
+    let mut start = 0usize;
+    let mut items = Vec::new();
+
+    for (idx, c) in text.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ';' if depth == 0 => {
+                items.push(&text[start..idx]);
+                start = idx + 1
+            },
+            _ => {},
+        }
+    }
+
+    items.push(&text[start..]);
+
+    items.iter().for_each(|item| {
+        if let Some((key, value)) = item.split_once(':') {
+            style.insert(key.trim().to_string(), quote_str!(value.trim()).into());
+        }
+    })
+}
+
+fn style_item(expr: &Expr, style: &mut Style) -> Option<()> {
+    match expr {
+        Expr::Lit(Lit::Str(Str { value, .. })) => parse_string_style(value, style),
+        Expr::Array(ArrayLit { elems, .. }) => {
+            for elem in elems.iter().flatten() {
+                match elem {
+                    ExprOrSpread {
+                        spread: Some(_), ..
+                    } => return None,
+                    ExprOrSpread { expr, .. } => style_item(expr, style)?,
+                }
+            }
+        },
+        Expr::Object(ObjectLit { props, .. }) => {
+            for (name, value) in key_values(props)? {
+                match value {
+                    Expr::Lit(lit @ (Lit::Str(_) | Lit::Num(_))) => {
+                        style.insert(name, lit.clone());
+                    },
+                    // nullish & boolean values are ignored
+                    value => {
+                        truthy(value)?;
+                    },
+                }
+            }
+        },
+        Expr::Paren(ParenExpr { expr, .. }) => style_item(expr, style)?,
+        expr => {
+            truthy(expr)?;
+        },
+    }
+
+    Some(())
+}
+
+/// ## [normalizeStyle](https://github.com/vuejs/core/blob/main/packages/shared/src/normalizeProp.ts)
+/// `None` if the style is not a constant string, array or object literal
+pub fn normalize_style(expr: &Expr) -> Option<Style> {
+    match expr {
+        Expr::Lit(Lit::Str(_)) | Expr::Array(_) | Expr::Object(_) | Expr::Paren(_) => {
+            let mut style = Style::new();
+
+            style_item(expr, &mut style)?;
+
+            Some(style)
+        },
+        _ => None,
+    }
+}
+
+fn hyphenate(name: &str) -> String {
+    if name.starts_with("--") {
+        name.to_string()
+    } else {
+        regex!(r"\B([A-Z])").replace_all(name, "-$1").to_lowercase()
+    }
+}
+
+/// ## [toHandlerKey](https://github.com/vuejs/core/blob/main/packages/shared/src/general.ts)
+/// `click` -> `onClick`
+pub fn to_handler_key(event: &str) -> String {
+    let mut chars = event.chars();
+
+    match chars.next() {
+        Some(first) => format!("on{}{}", first.to_uppercase(), chars.as_str()),
+        None => "on".to_string(),
+    }
+}
+
+/// `Click` -> `click`, event names of DOM listeners
+pub fn uncapitalize(name: &str) -> String {
+    let mut chars = name.chars();
+
+    match chars.next() {
+        Some(first) => format!("{}{}", first.to_lowercase(), chars.as_str()),
+        None => String::new(),
+    }
+}
+
+/// ## [stringifyStyle](https://github.com/vuejs/core/blob/main/packages/shared/src/normalizeProp.ts)
+pub fn stringify_style(style: &Style) -> String {
+    style
+        .iter()
+        .map(|(name, value)| {
+            let value = match value {
+                Lit::Num(Number { value, .. }) => stringify_number(*value),
+                Lit::Str(Str { value, .. }) => value.to_string(),
+                _ => String::new(),
+            };
+
+            format!("{}:{}", hyphenate(name), value)
+        })
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+pub fn style_object(style: Style) -> Expr {
+    let props = style
+        .into_iter()
+        .map(|(name, value)| Expr::from(value).into_prop(&name))
+        .collect();
+
+    ObjectLit {
+        span: DUMMY_SP,
+        props,
+    }
+    .into()
+}
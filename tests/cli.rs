@@ -0,0 +1,76 @@
+use std::{env, fs, process::Command};
+
+const BIN: &str = env!("CARGO_BIN_EXE_swc-vue-jsx");
+
+#[test]
+fn out_dir() {
+    let dir = env::temp_dir().join("swc-vue-jsx-cli");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("src/nested")).unwrap();
+
+    fs::write(
+        dir.join("src/a.tsx"),
+        "export const A = () => <div class={a}></div>;",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("src/nested/b.jsx"),
+        "export const B = () => <B />;",
+    )
+    .unwrap();
+
+    let status = Command::new(BIN)
+        .args(["--target", "vue2.7", "--source-maps", "-o"])
+        .arg(dir.join("out"))
+        .arg(dir.join("src"))
+        .status()
+        .unwrap();
+
+    assert!(status.success());
+
+    let code = fs::read_to_string(dir.join("out/a.js")).unwrap();
+
+    assert!(code.contains(r#"import { h } from "vue";"#));
+    assert!(code.ends_with("//# sourceMappingURL=a.js.map\n"));
+    assert!(dir.join("out/a.js.map").exists());
+    assert!(dir.join("out/nested/b.js").exists());
+}
+
+#[test]
+fn invalid_args() {
+    let output = Command::new(BIN)
+        .arg("--source-maps")
+        .arg("a.tsx")
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("requires `--out-dir`"));
+}
+
+#[test]
+fn typescript_stdout() {
+    let dir = env::temp_dir().join("swc-vue-jsx-cli-ts");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+
+    fs::write(
+        dir.join("typed.tsx"),
+        "interface Props { msg: string }\nconst App = (props: Props) => <div>{props.msg as string}</div>;",
+    )
+    .unwrap();
+
+    let output = Command::new(BIN)
+        .arg(dir.join("typed.tsx"))
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+
+    let code = String::from_utf8_lossy(&output.stdout);
+
+    assert!(code.starts_with(r#"import { createVNode } from "vue";"#));
+    assert!(!code.contains("interface"));
+    assert!(!code.contains(": Props"));
+    assert!(!code.contains(" as string"));
+}
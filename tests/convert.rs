@@ -2,15 +2,16 @@ use insta::assert_snapshot;
 use swc_core::{
     common::{chain, Mark},
     ecma::{
+        ast::{Module, ModuleItem, Program},
         parser::{Syntax, TsConfig},
         transforms::{
             base::{fixer::fixer, hygiene::hygiene, resolver},
             testing::Tester,
         },
-        visit::as_folder,
+        visit::FoldWith,
     },
 };
-use swc_plugin_vue_jsx::{PluginOptions, VueJSX};
+use swc_plugin_vue_jsx::{vue_jsx, PluginOptions};
 
 const TSX_SYNTAX: Syntax = Syntax::Typescript(TsConfig {
     tsx: true,
@@ -30,7 +31,7 @@ macro_rules! test {
                 let module = tester.apply_transform(
                     chain!(
                         resolver(unresolved_mark, Mark::new(), false),
-                        as_folder(VueJSX::new($opts, None, unresolved_mark)),
+                        vue_jsx($opts, Some(tester.comments.clone()), unresolved_mark),
                         hygiene(),
                         fixer(Some(&tester.comments.clone()))
                     ),
@@ -65,6 +66,47 @@ macro_rules! test {
     };
 }
 
+/// parse source as `Script`, e.g. CommonJS modules
+macro_rules! test_script {
+    ($name:ident, $src:literal, $opts:expr) => {
+        #[test]
+        #[allow(non_snake_case)]
+        fn $name() {
+            Tester::run(|tester| {
+                let unresolved_mark = Mark::new();
+
+                let script =
+                    tester.with_parser("test.tsx", TSX_SYNTAX, $src, |p| p.parse_script())?;
+
+                let program = Program::Script(script).fold_with(&mut chain!(
+                    resolver(unresolved_mark, Mark::new(), false),
+                    vue_jsx($opts, Some(tester.comments.clone()), unresolved_mark),
+                    hygiene(),
+                    fixer(Some(&tester.comments.clone()))
+                ));
+
+                let script = program.expect_script();
+
+                let module = Module {
+                    span: script.span,
+                    body: script.body.into_iter().map(ModuleItem::Stmt).collect(),
+                    shebang: script.shebang,
+                };
+
+                let code = tester.print(&module, &tester.comments.clone());
+
+                assert_snapshot!(stringify!($name), code);
+
+                Ok(())
+            });
+        }
+    };
+
+    ($name:ident, $src:literal) => {
+        test_script!($name, $src, PluginOptions::from("{}"));
+    };
+}
+
 test!(
     Tag:{
         native_div: r#"<div></div>"#,
@@ -79,11 +121,22 @@ test!(
         spread: r#"<div {...a}></div>"#,
         spread_with_prop: r#"<div {...a} class="cls"></div>"#,
         verify_symbol: r#"<div prop-name={a}></div>"#,
+        entity: r#"<div title="a &amp; b" prop={a}></div>"#,
+        double_entity: r#"<div title="&amp;lt;b&amp;gt;" prop={a}></div>"#,
+    },
+    MergeProps:{
+        class: r#"<div class="a" class={["b", { c: true }]}></div>"#,
+        class_dyn: r#"<div class="a" class={b}></div>"#,
+        style: r#"<div style="color: red" style={{ fontSize: "12px" }}></div>"#,
+        listener: r#"<div onClick={a} onClick={b} onClick={c}></div>"#,
+        spread: r#"let Btn; <Btn class="a" {...attrs} class={b}></Btn>"#,
+        override_prop: r#"<div id="a" id={b}></div>"#,
     },
     Event:{
         onClick: r#"<div onClick={fn}></div>"#,
         prefix_on: r#"<div onEvent={fn}></div>"#,
         namespace: r#"<div on:event={fn}></div>"#,
+        unicode: r#"<div onÉvent={fn} on:über={fn}></div>"#,
     },
     Directive:{
         vText: r#"<div v-text="text1"></div>"#,
@@ -99,6 +152,25 @@ test!(
         spread_child: r#"<div>{...a}</div>"#,
         jsx_child: r#"<div>{<div></div>}</div>"#,
         fragment_child: r#"<div>{<></>}</div>"#,
+        comment_child: r#"<div>{/* comment */}<span>{a}</span>{}</div>"#,
+        nested_fragment: r#"<div><>a<span>{b}</span><><i>{c}</i></></></div>"#,
+        attr_comment_child: r#"let A; <A slot=<div>{/* x */}</div> />"#,
+        attr_nested_fragment: r#"let A; <A slot=<><b /><>x</></> extra={<p>a{/* x */}b</p>} />"#,
+    },
+    Normalize:{
+        class_array: r#"<div class={["a", { b: true, c: false }, ["d", null]]}></div>"#,
+        class_dyn: r#"<div class={["a", { b: isB }]}></div>"#,
+        style_object: r#"<div style={{ color: "red", fontSize: 12 }}></div>"#,
+        style_string: r#"<div style="color: red; background: url(a;b.png)"></div>"#,
+        style_dyn: r#"<div style={[{ color: "red" }, s]}></div>"#,
+        cmpt_class_dyn: r#"let A; <A class={{ a: isA }}></A>"#,
+        static_content: r#"
+          <div class={a}>
+            <p class={["a", { b: true }]} style={{ color: "red", fontSize: "12px" }}>1</p>
+            <p style="--main-color: blue">2</p>
+            <p class="c">3</p>
+          </div>
+        "#,
     },
     PatchFlag:{
         dyn_class: r#"<div class={a}></div>"#,
@@ -108,6 +180,65 @@ test!(
         hydration_event: r#"<div onEvent={fn}></div>"#,
         need_patch: r#"<div ref={fn}></div>"#,
     },
+    ScopeHoist:{
+        setup_render: r#"
+          export default defineComponent({
+            setup() {
+              return () => <div>{cond ? <A /> : <A v-custom={x} />}</div>;
+            },
+          });
+        "#,
+        render_method: r#"
+          export default {
+            render() {
+              "use strict";
+              return <A>{items.map((item) => <A key={item} />)}</A>;
+            },
+          };
+        "#,
+        define_component_fn: r#"
+          export default defineComponent((props) => {
+            return () => <A />;
+          });
+        "#,
+        functional_component: r#"
+          const Comp = (props) => <B />;
+          function Other() {
+            return <B />;
+          }
+        "#,
+        fallback: r#"
+          function helper() {
+            return <A />;
+          }
+        "#,
+    },
+    ModuleHoist:{
+        directive: r#"
+          "use client";
+          import { ref } from "vue";
+          export const App = () => <div><span>text</span></div>;
+        "#,
+        late_import: r#"
+          import A from "./A";
+          const a = 1;
+          import B from "./B";
+          export const App = () => <div><span>text</span></div>;
+        "#,
+        load_time: r#"
+          const a = 1;
+          const b = <div><span>text</span></div>;
+          const c = <div><span>text</span></div>;
+        "#,
+        iife: r#"
+          import A from "./A";
+          const app = (() => <div><span>text</span>{later()}</div>)();
+          function later() {
+            return <div><span>text</span></div>;
+          }
+          import B from "./B";
+        "#,
+    },
     Fragment:{
         children: r#"
           <>
@@ -142,7 +273,69 @@ test!(
             <div></div>
             <div></div>
           </>
-        "#
+        "#,
+        nested_subtree: r#"
+          <div class={a}>
+            <table>
+              <tr><td>1</td></tr>
+              <tr><td>2</td></tr>
+            </table>
+          </div>
+        "#,
+    },
+    StaticContent:{
+        duplicate_attr: r#"
+          <div class={a}>
+            <p id="a" id="b">1</p>
+            <p>2</p>
+            <p>3</p>
+          </div>
+        "#,
+        escape: r#"
+          <div class={a}>
+            <p title="&quot;a&quot; &amp; 'b'">1 &lt; 2 &amp;&amp; 3</p>
+            <p title='<script>'>&nbsp;&copy;&#169;&#xA9;</p>
+            <p title={"&amp;"}>a</p>
+          </div>
+        "#,
+        double_entity: r#"
+          <div class={a}>
+            <p title="&amp;lt;script&amp;gt;">1</p>
+            <p>2</p>
+            <p>3</p>
+          </div>
+        "#,
+        adjacent_text: r#"<div class={a}>text<>more</><p>1</p><p>2</p><p>3</p></div>"#,
+        comment_text: r#"<div class={a}>hello {/* c */} world<span>1</span><span>2</span></div>"#,
+        bool_attr: r#"
+          <div class={a}>
+            <input disabled={false} />
+            <input checked={true} />
+            <input readonly={null} />
+            <input autofocus />
+            <input value={1} />
+          </div>
+        "#,
+        void_element: r#"
+          <div class={a}>
+            <br />
+            <hr />
+            <img src="a.png" />
+            <span>1</span>
+            <span>2</span>
+          </div>
+        "#,
+        table_part: r#"
+          <tbody class={a}>
+            <tr><td>1</td><td>2</td></tr>
+            <tr><td>3</td><td>4</td></tr>
+          </tbody>
+        "#,
+        paragraph_block: r#"
+          <div class={a}>
+            <p><div>1</div><div>2</div><div>3</div></p>
+          </div>
+        "#,
     }
 );
 
@@ -151,3 +344,437 @@ test!(
     r#"<custom-tag></custom-tag>"#,
     PluginOptions::from(r#"{ "customElementPatterns":["custom-tag"] }"#)
 );
+
+test!(
+    StaticVNode_size_limit,
+    r#"
+      <div class={a}>
+        <ul>
+          <li>1</li>
+          <li>2</li>
+        </ul>
+      </div>
+    "#,
+    PluginOptions::from(r#"{ "staticSizeLimit": 16 }"#)
+);
+
+test!(
+    MergeProps_disabled,
+    r#"let Btn; <Btn class="a" {...attrs} class={b} onClick={c} onClick={d}></Btn>"#,
+    PluginOptions::from(r#"{ "mergeProps": false }"#)
+);
+
+test_script!(
+    Script_require,
+    r#"
+      "use strict";
+      const a = require("./a");
+      module.exports = () => <div class={a}><A /></div>;
+    "#
+);
+
+test_script!(
+    Script_global,
+    r#"
+      var App = () => <div><span>text</span></div>;
+    "#,
+    PluginOptions::from(r#"{ "vueGlobal": "Vue" }"#)
+);
+
+test!(
+    RuntimeModule,
+    r#"<div v-show={a} class={b}><A /></div>"#,
+    PluginOptions::from(
+        r#"{ "runtimeModule": "@vue/runtime-core", "helperModules": { "vShow": "@vue/runtime-dom" } }"#
+    )
+);
+
+test_script!(
+    RuntimeModule_require,
+    r#"var App = () => <div v-show={a}></div>;"#,
+    PluginOptions::from(r#"{ "helperModules": { "vShow": "@vue/runtime-dom" } }"#)
+);
+
+test_script!(
+    RuntimeModule_global,
+    r#"var App = () => <div v-show={a} class={b}></div>;"#,
+    PluginOptions::from(
+        r#"{ "vueGlobal": "Vue", "helperModules": { "vShow": "@vue/runtime-dom" } }"#
+    )
+);
+
+test!(
+    CustomRenderer,
+    r#"
+      let Scene;
+      <Scene>
+        <mesh position={pos} v-show={visible}></mesh>
+        <group><text>1</text><text>2</text><text>3</text><text>4</text></group>
+      </Scene>
+    "#,
+    PluginOptions::from(r#"{ "customRenderer": true, "runtimeModule": "@vue/runtime-core" }"#)
+);
+
+test!(
+    CustomRenderer_hoist,
+    r#"
+      <scene>
+        <mesh name="box" />
+        <text>label</text>
+        <my-widget />
+        <group v-static><mesh position={[0, 1, 0]} /></group>
+        <mesh position={pos} />
+      </scene>
+    "#,
+    PluginOptions::from(
+        r#"{ "customRenderer": true, "customElementPatterns": ["^my-"], "runtimeModule": "@vue/runtime-core" }"#
+    )
+);
+
+test!(
+    Target_vue2,
+    r#"
+      let Comp;
+      <div id="app" class={cls} style={sty} key={k} onClick={onClick} v-show={visible} v-focus={f} innerHTML={html}>
+        text
+        <Comp v-model={value} v-model:title={title} v-slots={slots} {...attrs}></Comp>
+        <input v-model={text} />
+        <Unresolved />
+        {expr}
+      </div>
+    "#,
+    PluginOptions::from(r#"{ "target": "vue2.7" }"#)
+);
+
+test!(
+    Target_vue2_spread,
+    r#"
+      let Comp;
+      <div>
+        <input {...{ attrs: a, on: { input: g } }} id="x" class="c" onInput={f} />
+        <Comp {...data} {...{ on: listeners }} title={title} onChange={f} />
+        <span {...data} />
+      </div>
+    "#,
+    PluginOptions::from(r#"{ "target": "vue2.7" }"#)
+);
+
+test!(
+    Target_vapor,
+    r#"
+      let Comp;
+      <div id="app" class={cls} style={sty} onClick={onClick} v-show={visible} v-focus={f}>
+        <p class="static">a &amp; b</p>
+        count: {count} times
+        <Comp v-model={value} title={title} {...attrs}>slot {msg}</Comp>
+        <input v-model={text} ref={input} />
+        <span v-html={html}></span>
+        {list.map((item) => <li>{item}</li>)}
+      </div>;
+      <>text<br />{msg}</>
+    "#,
+    PluginOptions::from(r#"{ "target": "vapor" }"#)
+);
+
+test!(
+    Target_vapor_component_event,
+    r#"
+      let Comp;
+      <div>
+        <Comp on:click={f} onUpdate={g} />
+      </div>
+    "#,
+    PluginOptions::from(r#"{ "target": "vapor" }"#)
+);
+
+test!(
+    Target_vapor_normalize,
+    r#"
+      <div>
+        {/* comment */}
+        <>a {b}<span>{c}</span></>
+      </div>
+    "#,
+    PluginOptions::from(r#"{ "target": "vapor" }"#)
+);
+
+test!(
+    JSXRuntime_automatic,
+    r#"
+      let Comp;
+      <div id="app" class={cls} key={k} onClick={onClick} v-show={visible} disabled {...attrs}>
+        text
+        <Comp title={<span>title</span>}>{msg}</Comp>
+        <my-element />
+        {...list}
+      </div>;
+      <>{msg}</>
+    "#,
+    PluginOptions::from(r#"{ "jsxRuntime": "automatic", "jsxImportSource": "@vue" }"#)
+);
+
+test!(
+    JSXRuntime_automatic_event,
+    r#"
+      <div on:click={f} onClick={g} onClick={h} on:über={u} />;
+      <div onClick={f} {...attrs} onClick={g} />
+    "#,
+    PluginOptions::from(r#"{ "jsxRuntime": "automatic" }"#)
+);
+
+test!(
+    Hoist_disabled,
+    r#"
+      <div>
+        <p class="static">text</p>
+        <div><span>a</span><span>b</span><span>c</span><span>d</span><span>e</span></div>
+        {msg}
+      </div>
+    "#,
+    PluginOptions::from(r#"{ "hoist": false }"#)
+);
+
+test!(
+    Hint_static_dynamic,
+    r#"
+      import { ICON, LABEL } from "./icons";
+      <div>
+        <i v-static class={ICON}><span>{LABEL}</span></i>
+        <p v-static><b>{LABEL}</b><span v-dynamic>{LABEL}</span></p>
+        <div v-dynamic><span>a</span><span>b</span><span>c</span><span>d</span><span>e</span></div>
+        {msg}
+      </div>
+    "#,
+    PluginOptions::default()
+);
+
+test!(
+    Hoist_eligibility,
+    r#"
+      const handler = () => {};
+      <div>
+        <span ref="el">ref</span>
+        <span key="k">key</span>
+        <span onClick={handler}>event</span>
+        <span onClick="handler">event literal</span>
+        <Comp v-slots={{ default: () => "slot" }} />
+        <input v-model:title={t} />
+        <span id="static">static</span>
+      </div>
+    "#,
+    PluginOptions::default()
+);
+
+test!(
+    List,
+    r#"
+      <ul>
+        {items.map((item) => <li key={item.id}>{item.name}</li>)}
+        {items.map((item, idx) => (<li>{idx}</li>))}
+        {items.map(function (item) {
+          if (item.hidden) {
+            return <li key={item.id} class="hidden" />;
+          }
+          return <li key={item.id}>{item.name}</li>;
+        })}
+        {items.map((item, idx, arr) => <li key={item.id}>{arr.length}</li>)}
+        {items.map((item) => item.visible && <li key={item.id} />)}
+      </ul>
+    "#,
+    PluginOptions::default()
+);
+
+test!(
+    List_mixed_keys,
+    r#"
+      <ul>
+        {items.map((item) => {
+          if (item.id) {
+            return <li key={item.id} />;
+          }
+          return <li />;
+        })}
+      </ul>
+    "#,
+    PluginOptions::default()
+);
+
+test!(
+    List_spread,
+    r#"
+      <ul>
+        {items.map((item) => <li {...item.props} />)}
+      </ul>
+    "#,
+    PluginOptions::default()
+);
+
+test!(
+    Condition,
+    r#"
+      let Comp;
+      <div>
+        {ok ? <Comp msg="a" /> : <Comp msg="b" />}
+        {ok && <span>yes</span>}
+        {a ? <p key="a" /> : b ? <p /> : null}
+        {(ok) ? (<i />) : undefined}
+        {ok ? "text" : <span />}
+        {ok || <span />}
+      </div>
+    "#,
+    PluginOptions::default()
+);
+
+test!(
+    Fragment_explicit,
+    r#"
+      import { Fragment } from "vue";
+      import { Fragment as F } from "./other";
+      <>
+        <span>{a}</span>
+        {b}
+      </>;
+      <>{...list}</>;
+      <><span>{a}</span></>;
+      <div>
+        <Fragment key={k}><span>{a}</span></Fragment>
+        <Fragment><i /><b /></Fragment>
+        <F key={k}>slot</F>
+      </div>;
+    "#,
+    PluginOptions::default()
+);
+
+test!(
+    Ref_key_non_ref_binding,
+    r#"
+      export default defineComponent({
+        setup() {
+          const el = shallowRef();
+          const handler = () => {};
+          const count = 0;
+          let later;
+          return () => (
+            <div>
+              <span ref="el" />
+              <span ref="handler" />
+              <span ref="count" />
+              <span ref="later" />
+            </div>
+          );
+        },
+      });
+    "#,
+    PluginOptions::default()
+);
+
+test!(
+    Ref_loop_and_key,
+    r#"
+      export default defineComponent({
+        setup() {
+          const input = ref();
+          const items = ref([]);
+          return () => (
+            <div>
+              <input ref="input" />
+              <span ref="other" />
+              <ul>{items.value.map((item) => <li ref="items">{item}</li>)}</ul>
+              {items.value.map((item) => <Comp>{() => <i ref={setRef} />}</Comp>)}
+            </div>
+          );
+        },
+      });
+      function render() {
+        const rows = [];
+        for (const row of list) {
+          rows.push(<tr ref={rowRef} />);
+        }
+        return <table ref={tableRef}>{rows}</table>;
+      }
+    "#,
+    PluginOptions::default()
+);
+
+test!(
+    Pragma:{
+        options: r#"
+          /* @vue-jsx hoist: false, staticThreshold: 2 */
+          const a = <div><span>1</span><span>2</span></div>;
+        "#,
+        ignore_file: r#"
+          /* @vue-jsx-ignore */
+          const a = <div class={b}></div>;
+        "#,
+        ignore_expr: r#"
+          const a = <div class={b}></div>;
+          const c = /* @vue-jsx-ignore */ <div class={d}></div>;
+        "#,
+        jsx_import_source: r#"
+          /** @jsxImportSource vue */
+          const a = <div class={b}>{c}</div>;
+        "#,
+        jsx_runtime: r#"
+          /** @jsxRuntime automatic */
+          const a = <div class={b}>{c}</div>;
+        "#,
+        jsx_runtime_optimized: r#"
+          /** @jsxImportSource vue */
+          /** @jsxRuntime optimized */
+          const a = <div class={b}>{c}</div>;
+        "#,
+        options_jsx_runtime: r#"
+          /* @vue-jsx jsxRuntime: "automatic" */
+          const a = <div class={b}>{c}</div>;
+        "#,
+        options_target: r#"
+          /* @vue-jsx target: "vue2.7", mergeProps: false */
+          const a = <div class="a" class={b} onClick={c}></div>;
+        "#,
+    }
+);
+
+test!(
+    VueVersion_3_1,
+    r#"<div><span>text</span><span onClick={a}></span></div>"#,
+    PluginOptions::from(r#"{ "vueVersion": "3.1" }"#)
+);
+
+test!(
+    VueVersion_3_4,
+    r#"<div><span>text</span><input onInput={a} /></div>"#,
+    PluginOptions::from(r#"{ "vueVersion": "3.4" }"#)
+);
+
+test!(
+    Ssr,
+    r#"
+      let Comp;
+      export const A = defineComponent({
+        setup() {
+          return () => (
+            <div id="app" class={cls} onClick={onClick} v-show={visible}>
+              <p class="static">a &amp; b</p>
+              text {msg} {`tpl`}
+              <Comp title={title}>slot</Comp>
+              <span v-html={html}></span>
+              <input v-model={value} disabled />
+              {list.map((item) => <li key={item}>{item}</li>)}
+              {list.map(function (item) {
+                if (item.hidden) {
+                  return <li class="hidden" />;
+                }
+                return <li>{item.name}</li>;
+              })}
+              {list.map((item, idx, arr) => <li>{arr.length}</li>)}
+              {list.map((item) => <li>{/* comment */}<>{item}</></li>)}
+            </div>
+          );
+        },
+      });
+      export const B = defineComponent((props) => {
+        return () => <Comp {...props} />;
+      });
+    "#,
+    PluginOptions::from(r#"{ "ssr": true }"#)
+);
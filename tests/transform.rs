@@ -0,0 +1,120 @@
+use insta::assert_snapshot;
+use swc_plugin_vue_jsx::{transform, PluginOptions};
+
+#[test]
+fn tsx() {
+    let output = transform(
+        r#"const a: number = 1; export const App = () => <div class={a}>text</div>;"#,
+        "App.tsx",
+        PluginOptions::default(),
+    )
+    .unwrap();
+
+    assert!(output.map.contains(r#""sources":["App.tsx"]"#));
+    assert!(output.diagnostics.is_empty());
+    assert!(!output.code.contains(": number"));
+
+    assert_snapshot!(output.code);
+}
+
+#[test]
+fn jsx() {
+    let output = transform(
+        r#"export const App = () => <div class={a}>text</div>;"#,
+        "App.jsx",
+        PluginOptions::default(),
+    )
+    .unwrap();
+
+    assert_snapshot!(output.code);
+}
+
+#[test]
+fn without_module_syntax() {
+    let output = transform(
+        r#"const App = () => <div class={a}>text</div>;"#,
+        "App.jsx",
+        PluginOptions::default(),
+    )
+    .unwrap();
+
+    assert!(output.code.starts_with("import {"));
+    assert!(!output.code.contains("require("));
+}
+
+#[test]
+fn syntax_error() {
+    let err = transform(
+        r#"const App = () => <div>"#,
+        "App.tsx",
+        PluginOptions::default(),
+    )
+    .err()
+    .unwrap();
+
+    assert!(!err.diagnostics.is_empty());
+}
+
+#[test]
+fn invalid_pragma() {
+    let output = transform(
+        "/* @vue-jsx hoist: 1 */\nconst a = <div></div>;",
+        "App.tsx",
+        PluginOptions::default(),
+    )
+    .unwrap();
+
+    let diagnostic = &output.diagnostics[0];
+
+    assert!(diagnostic.is_error());
+    assert_eq!(diagnostic.location, Some((1, 1)));
+    assert!(diagnostic.message.contains("`hoist`"));
+}
+
+#[test]
+fn unknown_pragma_key() {
+    let output = transform(
+        "/* @vue-jsx hoist: false, statcThreshold: 2 */\nconst a = <div></div>;",
+        "App.tsx",
+        PluginOptions::default(),
+    )
+    .unwrap();
+
+    let diagnostic = &output.diagnostics[0];
+
+    assert!(diagnostic.is_error());
+    assert_eq!(diagnostic.location, Some((1, 1)));
+    assert!(diagnostic.message.contains("`statcThreshold`"));
+}
+
+#[test]
+fn namespaced_tag() {
+    let output = transform(
+        "const a = <div>\n  <svg:rect />\n</div>;",
+        "App.tsx",
+        PluginOptions::default(),
+    )
+    .unwrap();
+
+    let diagnostic = &output.diagnostics[0];
+
+    assert!(diagnostic.is_error());
+    assert_eq!(diagnostic.location, Some((2, 4)));
+}
+
+#[test]
+fn invalid_options() {
+    let err = PluginOptions::parse(r#"{ "ssr": true, "target": "vue4" }"#).unwrap_err();
+
+    assert!(err.starts_with("`target`: unknown variant `vue4`"));
+
+    let opts = PluginOptions::parse(r#"{ "customElementPatterns": ["("] }"#).unwrap();
+
+    let output = transform("const a = <div></div>;", "App.tsx", opts).unwrap();
+
+    assert!(
+        output.diagnostics[0]
+            .message
+            .contains("`customElementPatterns`")
+    );
+}